@@ -0,0 +1,118 @@
+use super::multiset::Multiset;
+use super::{Constraint, YesNoMaybe};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/****************************
+ * Constraint: AllDifferent *
+ ****************************/
+
+/// The constraint that every variable in the group holds a distinct value, without requiring
+/// (unlike `Permutation`) that those values come from some predetermined range: each variable is
+/// free to draw from its own domain, as long as no two end up equal.
+///
+/// Pruning only catches the case where two or more variables have *already* been forced to the
+/// same value (a size-1 Hall violation): the `and`/`or`-folded `Set` this constraint works with
+/// sums up, for each value, how many variables are guaranteed to take it, but — like every
+/// `Constraint` impl, which only ever sees that aggregate, not which variable contributed what —
+/// it can't single out a size-`k` subset of variables and notice that their domains only cover
+/// `k` values between them. Catching that in general needs a dedicated Hall-interval pass over
+/// the table's per-variable domains directly, which the `Constraint` trait's folded `Set` doesn't
+/// expose.
+#[derive(Debug, Clone)]
+pub struct AllDifferent<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> AllDifferent<T> {
+    pub fn new() -> AllDifferent<T> {
+        AllDifferent {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Default
+    for AllDifferent<T>
+{
+    fn default() -> AllDifferent<T> {
+        AllDifferent::new()
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constraint<T>
+    for AllDifferent<T>
+{
+    const NAME: &'static str = "AllDifferent";
+
+    type Set = DistinctRange<T>;
+
+    fn singleton(&self, _index: usize, elem: T) -> Self::Set {
+        DistinctRange::singleton(elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.and(b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.or(b)
+    }
+
+    fn check(&self, set: Self::Set) -> YesNoMaybe {
+        set.all_distinct()
+    }
+}
+
+/************************
+ *   DistinctRange      *
+ ************************/
+
+/// The range `[min, max]` of how many times each value could still appear across the folded
+/// variables: `min` is the count that's guaranteed no matter how undecided variables resolve,
+/// `max` is the most that could possibly happen. `min`'s count for a value reaching 2 or more is
+/// an unconditional violation; a fully-decided group (`min == max`) with every count at most 1 is
+/// a satisfying assignment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistinctRange<T: Ord> {
+    min: Multiset<T>,
+    max: Multiset<T>,
+}
+
+impl<T: Ord + Clone> DistinctRange<T> {
+    fn singleton(elem: T) -> DistinctRange<T> {
+        DistinctRange {
+            min: Multiset::singleton(elem.clone()),
+            max: Multiset::singleton(elem),
+        }
+    }
+
+    /// Fold two variables' contributions together: their guaranteed/possible counts simply add.
+    fn and(self, other: DistinctRange<T>) -> DistinctRange<T> {
+        DistinctRange {
+            min: self.min.sum(other.min),
+            max: self.max.sum(other.max),
+        }
+    }
+
+    /// Fold two candidate values within the same variable's domain: only a value common to both
+    /// alternatives is guaranteed, and the variable can contribute at most one of either.
+    fn or(self, other: DistinctRange<T>) -> DistinctRange<T> {
+        DistinctRange {
+            min: self.min.intersection(other.min),
+            max: self.max.union(other.max),
+        }
+    }
+
+    fn all_distinct(&self) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        if self.min.counts().any(|count| *count >= 2) {
+            No
+        } else if self.min == self.max {
+            Yes
+        } else {
+            Maybe
+        }
+    }
+}
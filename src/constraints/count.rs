@@ -3,24 +3,28 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 
-pub struct Count<N: Debug + Hash + Eq + Clone + Sized + 'static> {
+/// Global cardinality: bounds how many of the posted variables take each of several values, all
+/// at once. Carries a `value -> (lo, hi)` map (the "count occurrences per key" idiom) and ANDs
+/// the per-value verdicts together. For a single value, [`Count`](super::Count) reads more
+/// directly off a `BagRange`; reach for this one when several values each need their own bound.
+pub struct GlobalCardinality<N: Debug + Hash + Eq + Clone + Sized + 'static> {
     count_limits: HashMap<N, (u32, u32)>,
 }
 
-impl<N: Debug + Hash + Eq + Clone + Sized + 'static> Count<N> {
-    pub fn new(count_limits: impl IntoIterator<Item = (N, u32, u32)>) -> Count<N> {
+impl<N: Debug + Hash + Eq + Clone + Sized + 'static> GlobalCardinality<N> {
+    pub fn new(count_limits: impl IntoIterator<Item = (N, u32, u32)>) -> GlobalCardinality<N> {
         let mut map = HashMap::new();
         for (var, min, max) in count_limits {
             map.insert(var, (min, max));
         }
-        Count { count_limits: map }
+        GlobalCardinality { count_limits: map }
     }
 }
 
-impl<N: Debug + Hash + Eq + Clone + Sized + 'static> Constraint<N> for Count<N> {
+impl<N: Debug + Hash + Eq + Clone + Sized + 'static> Constraint<N> for GlobalCardinality<N> {
     type Set = HashMap<N, (u32, u32)>;
 
-    const NAME: &'static str = "Count";
+    const NAME: &'static str = "GlobalCardinality";
 
     fn singleton(&self, _index: usize, var: N) -> Self::Set {
         HashMap::from([(var, (1, 1))])
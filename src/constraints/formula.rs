@@ -0,0 +1,190 @@
+use super::{Constraint, YesNoMaybe};
+use std::fmt::Debug;
+
+/// An expression over a `Formula` constraint's parameter cells (`Var(i)` refers to the `i`-th
+/// one), plus integer/boolean literals and lifted arithmetic/boolean operators. Evaluates to
+/// either an integer or a boolean; mixing the two (e.g. adding a comparison) panics at eval
+/// time, same as `Pred`'s boxed closure would panic on a type error in the predicate itself.
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Var(usize),
+    Int(i64),
+    Bool(bool),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Sum(Vec<Expr>),
+    Eq(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Le(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+impl Expr {
+    pub fn var(index: usize) -> Expr {
+        Expr::Var(index)
+    }
+
+    pub fn int(n: i64) -> Expr {
+        Expr::Int(n)
+    }
+
+    pub fn sum(exprs: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::Sum(exprs.into_iter().collect())
+    }
+
+    pub fn add(self, other: Expr) -> Expr {
+        Expr::Add(Box::new(self), Box::new(other))
+    }
+
+    pub fn mul(self, other: Expr) -> Expr {
+        Expr::Mul(Box::new(self), Box::new(other))
+    }
+
+    pub fn eq(self, other: Expr) -> Expr {
+        Expr::Eq(Box::new(self), Box::new(other))
+    }
+
+    pub fn lt(self, other: Expr) -> Expr {
+        Expr::Lt(Box::new(self), Box::new(other))
+    }
+
+    pub fn le(self, other: Expr) -> Expr {
+        Expr::Le(Box::new(self), Box::new(other))
+    }
+
+    pub fn and(self, other: Expr) -> Expr {
+        Expr::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Expr) -> Expr {
+        Expr::Or(Box::new(self), Box::new(other))
+    }
+
+    pub fn not(self) -> Expr {
+        Expr::Not(Box::new(self))
+    }
+
+    fn eval(&self, env: &[i64]) -> ExprValue {
+        use Expr::*;
+
+        match self {
+            Var(i) => ExprValue::Int(env[*i]),
+            Int(n) => ExprValue::Int(*n),
+            Bool(b) => ExprValue::Bool(*b),
+            Add(a, b) => ExprValue::Int(a.eval(env).as_int() + b.eval(env).as_int()),
+            Mul(a, b) => ExprValue::Int(a.eval(env).as_int() * b.eval(env).as_int()),
+            Sum(exprs) => ExprValue::Int(exprs.iter().map(|e| e.eval(env).as_int()).sum()),
+            Eq(a, b) => ExprValue::Bool(a.eval(env).as_int() == b.eval(env).as_int()),
+            Lt(a, b) => ExprValue::Bool(a.eval(env).as_int() < b.eval(env).as_int()),
+            Le(a, b) => ExprValue::Bool(a.eval(env).as_int() <= b.eval(env).as_int()),
+            And(a, b) => ExprValue::Bool(a.eval(env).as_bool() && b.eval(env).as_bool()),
+            Or(a, b) => ExprValue::Bool(a.eval(env).as_bool() || b.eval(env).as_bool()),
+            Not(a) => ExprValue::Bool(!a.eval(env).as_bool()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExprValue {
+    Int(i64),
+    Bool(bool),
+}
+
+impl ExprValue {
+    fn as_int(self) -> i64 {
+        match self {
+            ExprValue::Int(n) => n,
+            ExprValue::Bool(_) => panic!("Formula: expected an integer expression, got a boolean one"),
+        }
+    }
+
+    fn as_bool(self) -> bool {
+        match self {
+            ExprValue::Bool(b) => b,
+            ExprValue::Int(_) => panic!("Formula: expected a boolean expression, got an integer one"),
+        }
+    }
+}
+
+/// The constraint that a boolean-valued `Expr` holds over its parameter cells. Realizes the
+/// "make constraints into formulas" TODO: a composable, data-driven alternative to hand-writing
+/// a `Box<dyn Fn>` predicate as `Pred` requires.
+#[derive(Debug, Clone)]
+pub struct Formula {
+    num_params: usize,
+    expr: Expr,
+}
+
+impl Formula {
+    pub fn new(num_params: usize, expr: Expr) -> Formula {
+        Formula { num_params, expr }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone + Into<i64> + Send + Sync + Sized + 'static> Constraint<T>
+    for Formula
+{
+    type Set = Vec<Option<i64>>;
+
+    const NAME: &'static str = "Formula";
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        let mut result = vec![None; self.num_params];
+        result[index] = Some(elem.into());
+        result
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        let mut result = a;
+        for (i, elem) in b.into_iter().enumerate() {
+            if let Some(elem) = elem {
+                result[i] = Some(elem);
+            }
+        }
+        result
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        let mut result = a;
+        for (i, elem) in b.into_iter().enumerate() {
+            if result[i] != elem {
+                result[i] = None;
+            }
+        }
+        result
+    }
+
+    fn check(&self, set: Self::Set) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        let env = match set.into_iter().collect::<Option<Vec<i64>>>() {
+            Some(env) => env,
+            None => return Maybe,
+        };
+        if self.expr.eval(&env).as_bool() {
+            Yes
+        } else {
+            No
+        }
+    }
+}
+
+#[test]
+fn test_formula() {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    // a + b == 4
+    let formula = Formula::new(2, Expr::var(0).add(Expr::var(1)).eq(Expr::int(4)));
+
+    assert_eq!(formula.singleton(0, 1i32), vec![Some(1), None]);
+    assert_eq!(
+        Constraint::<i32>::and(&formula, formula.singleton(0, 1i32), formula.singleton(1, 3i32)),
+        vec![Some(1), Some(3)]
+    );
+
+    assert_eq!(Constraint::<i32>::check(&formula, vec![None, None]), Maybe);
+    assert_eq!(Constraint::<i32>::check(&formula, vec![Some(1), Some(3)]), Yes);
+    assert_eq!(Constraint::<i32>::check(&formula, vec![Some(1), Some(2)]), No);
+}
@@ -0,0 +1,78 @@
+use super::{Constraint, YesNoMaybe};
+use std::fmt::Debug;
+
+/// The constraint `lo <= sum(coeff_i * x_i) <= hi`, with integer coefficients and inclusive
+/// bounds. Used for magic-square running sums and skyscraper visibility counts: `Self::Set`
+/// tracks the achievable range of the (partial) sum, so `check` prunes by bound consistency
+/// instead of enumerating every combination of domains.
+#[derive(Debug, Clone)]
+pub struct Linear {
+    coeffs: Vec<i64>,
+    lo: i64,
+    hi: i64,
+}
+
+impl Linear {
+    pub fn new(coeffs: impl IntoIterator<Item = i64>, lo: i64, hi: i64) -> Linear {
+        Linear {
+            coeffs: coeffs.into_iter().collect(),
+            lo,
+            hi,
+        }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone + Into<i64> + Send + Sync + Sized + 'static> Constraint<T>
+    for Linear
+{
+    /// The range `(min, max)` of achievable sums so far.
+    type Set = (i64, i64);
+
+    const NAME: &'static str = "Linear";
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        let value = self.coeffs[index] * elem.into();
+        (value, value)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        (a.0 + b.0, a.1 + b.1)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        (a.0.min(b.0), a.1.max(b.1))
+    }
+
+    fn check(&self, (min, max): Self::Set) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        if max < self.lo || min > self.hi {
+            No
+        } else if min >= self.lo && max <= self.hi {
+            Yes
+        } else {
+            Maybe
+        }
+    }
+}
+
+#[test]
+fn test_linear() {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    // 1*a + 1*b in [4, 4], i.e. a + b == 4.
+    let linear = Linear::new([1, 1], 4, 4);
+
+    let a: i32 = 1;
+    let b: i32 = 3;
+    assert_eq!(linear.singleton(0, a), (1, 1));
+    assert_eq!(
+        Constraint::<i32>::and(&linear, linear.singleton(0, a), linear.singleton(1, b)),
+        (4, 4)
+    );
+
+    assert_eq!(Constraint::<i32>::check(&linear, (4, 4)), Yes);
+    assert_eq!(Constraint::<i32>::check(&linear, (2, 6)), Maybe);
+    assert_eq!(Constraint::<i32>::check(&linear, (5, 7)), No);
+    assert_eq!(Constraint::<i32>::check(&linear, (1, 3)), No);
+}
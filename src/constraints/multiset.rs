@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+/// A multiset of `T`, represented as value -> count. Shared by every `Constraint` whose `Set`
+/// folds a range of possible multisets over `and`/`or` (`AllDifferent`'s `DistinctRange`,
+/// `Shuffle`'s `MultisetRange`): both track the same "min guaranteed / max possible count per
+/// value" shape, so there's one `Multiset` instead of two copies drifting apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Multiset<T: Ord>(BTreeMap<T, usize>);
+
+impl<T: Ord + Clone> Multiset<T> {
+    pub(super) fn empty() -> Multiset<T> {
+        Multiset(BTreeMap::new())
+    }
+
+    pub(super) fn singleton(elem: T) -> Multiset<T> {
+        Multiset(BTreeMap::from([(elem, 1)]))
+    }
+
+    pub(super) fn count(&self, value: &T) -> usize {
+        self.0.get(value).copied().unwrap_or(0)
+    }
+
+    pub(super) fn keys(&self) -> impl Iterator<Item = &T> {
+        self.0.keys()
+    }
+
+    pub(super) fn counts(&self) -> impl Iterator<Item = &usize> {
+        self.0.values()
+    }
+
+    /// Combine two multisets, adding counts: the multiset of everything in both.
+    pub(super) fn sum(mut self, other: Multiset<T>) -> Multiset<T> {
+        for (elem, count) in other.0 {
+            *self.0.entry(elem).or_insert(0) += count;
+        }
+        self
+    }
+
+    /// The multiset with, for each value, the larger of the two counts.
+    pub(super) fn union(mut self, other: Multiset<T>) -> Multiset<T> {
+        for (elem, count) in other.0 {
+            let entry = self.0.entry(elem).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+        self
+    }
+
+    /// The multiset with, for each value, the smaller of the two counts.
+    pub(super) fn intersection(self, other: Multiset<T>) -> Multiset<T> {
+        let mut result = BTreeMap::new();
+        for (elem, count) in self.0 {
+            let min_count = count.min(other.count(&elem));
+            if min_count > 0 {
+                result.insert(elem, min_count);
+            }
+        }
+        Multiset(result)
+    }
+}
+
+#[test]
+fn test_multiset() {
+    fn multiset(chars: &str) -> Multiset<char> {
+        chars.chars().fold(Multiset::empty(), |m, ch| m.sum(Multiset::singleton(ch)))
+    }
+
+    assert_eq!(multiset("aab").sum(multiset("ab")), multiset("aaabb"));
+    assert_eq!(multiset("aab").union(multiset("abb")), multiset("aabb"));
+    assert_eq!(multiset("aabbc").intersection(multiset("abbbd")), multiset("abb"));
+}
@@ -1,6 +1,7 @@
 use super::{Constraint, YesNoMaybe};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::ops::RangeInclusive;
 
 /**********************
  * Constraint: Subset *
@@ -125,6 +126,145 @@ impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constra
     }
 }
 
+/************************
+ * Constraint: Disjoint *
+ ************************/
+
+/// The constraint that `{X1, ..., Xn}` shares no value with `set`.
+#[derive(Debug, Clone)]
+pub struct Disjoint<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static>(Bag<T>);
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Disjoint<T> {
+    pub fn new(set: impl IntoIterator<Item = T>) -> Disjoint<T> {
+        Disjoint(Bag::new(set))
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constraint<T>
+    for Disjoint<T>
+{
+    type Set = BagRange<T>;
+
+    const NAME: &'static str = "Disjoint";
+
+    fn singleton(&self, _index: usize, elem: T) -> Self::Set {
+        BagRange::singleton(elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.and(b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.or(b)
+    }
+
+    fn check(&self, range: Self::Set) -> YesNoMaybe {
+        range.is_disjoint(&self.0)
+    }
+}
+
+/************************
+ * Constraint: Distinct *
+ ************************/
+
+/// The constraint that every variable in the group holds a distinct value.
+///
+/// Same semantics as [`AllDifferent`](super::AllDifferent), built on this module's `Bag`/
+/// `BagRange` instead of a separate count-based multiset, so it composes cheaply with
+/// `Subset`/`Superset`/`Disjoint` when those are already in play on the same group of variables.
+/// Prefer `AllDifferent` on its own; reach for this one only alongside the other `Bag`-based
+/// constraints above.
+#[derive(Debug, Clone)]
+pub struct Distinct<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> {
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Distinct<T> {
+    pub fn new() -> Distinct<T> {
+        Distinct {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Default for Distinct<T> {
+    fn default() -> Distinct<T> {
+        Distinct::new()
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constraint<T>
+    for Distinct<T>
+{
+    type Set = BagRange<T>;
+
+    const NAME: &'static str = "Distinct";
+
+    fn singleton(&self, _index: usize, elem: T) -> Self::Set {
+        BagRange::singleton(elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.and(b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.or(b)
+    }
+
+    fn check(&self, range: Self::Set) -> YesNoMaybe {
+        range.all_distinct()
+    }
+}
+
+/*********************
+ * Constraint: Count *
+ *********************/
+
+/// The constraint that `lo..=hi` of the posted variables hold `value`. For bounding several
+/// values at once, see [`GlobalCardinality`](super::GlobalCardinality).
+#[derive(Debug, Clone)]
+pub struct Count<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> {
+    value: T,
+    lo: u32,
+    hi: u32,
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Count<T> {
+    pub fn new(value: T, count: RangeInclusive<u32>) -> Count<T> {
+        Count {
+            value,
+            lo: *count.start(),
+            hi: *count.end(),
+        }
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constraint<T>
+    for Count<T>
+{
+    type Set = BagRange<T>;
+
+    const NAME: &'static str = "Count";
+
+    fn singleton(&self, _index: usize, elem: T) -> Self::Set {
+        BagRange::singleton(elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.and(b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        a.or(b)
+    }
+
+    fn check(&self, range: Self::Set) -> YesNoMaybe {
+        range.count(&self.value, self.lo, self.hi)
+    }
+}
+
 /***************************
  * Constraint: Permutation *
  ***************************/
@@ -171,6 +311,14 @@ impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constra
  *     Bag Range        *
  ************************/
 
+/// The three-valued (`YesNoMaybe`) set lattice this crate actually uses for `Subset`/`Superset`/
+/// `Permutation`/`AllDifferent`-style reasoning: `min`/`max` bracket the multiset the folded
+/// variables could still resolve to, and `is_subset`/`is_superset`/`is_equal`/`is_disjoint`/
+/// `count`/`all_distinct` all derive their `Yes`/`No`/`Maybe` verdict from where `min` and `max`
+/// fall relative to the thing being compared against. A separate, never-wired-up `Bag` enum with
+/// `Multiset`/`Range`/`Set`/`Single` variants was once sketched out against this same idea but
+/// abandoned half-finished (and has since been deleted); this `BagRange`-over-`Bag` pair is what
+/// ended up carrying the lattice in practice, so there's no second lattice left to complete.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BagRange<T: Ord> {
     min: Bag<T>,
@@ -236,6 +384,51 @@ impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> BagRang
             No
         }
     }
+
+    fn is_disjoint(&self, other: &Bag<T>) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        if !self.max.intersects(other) {
+            Yes
+        } else if self.min.intersects(other) {
+            No
+        } else {
+            Maybe
+        }
+    }
+
+    /// How many of the folded variables are guaranteed/could possibly hold `value`? `Yes` when
+    /// every feasible count already lands in `lo..=hi`, `No` when even the best case can't, else
+    /// `Maybe`.
+    fn count(&self, value: &T, lo: u32, hi: u32) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        let actual_min = self.min.count(value) as u32;
+        let actual_max = self.max.count(value) as u32;
+        if actual_min > hi || actual_max < lo {
+            No
+        } else if actual_min >= lo && actual_max <= hi {
+            Yes
+        } else {
+            Maybe
+        }
+    }
+
+    /// Are the folded variables pairwise distinct? `min` is the multiset of values guaranteed to
+    /// appear no matter how undecided variables resolve: a value appearing twice there is an
+    /// unconditional violation. `max` is the most that could appear; `min == max` means every
+    /// variable is fully decided, so a `min` free of repeats is a satisfying assignment.
+    fn all_distinct(&self) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        if self.min.has_duplicate() {
+            No
+        } else if self.min == self.max {
+            Yes
+        } else {
+            Maybe
+        }
+    }
 }
 
 /************************
@@ -340,6 +533,76 @@ impl<T: Ord> Bag<T> {
         }
         true
     }
+
+    /// Do `self` and `other` share at least one element?
+    fn intersects(&self, other: &Bag<T>) -> bool {
+        let mut other_iter = other.0.iter().peekable();
+
+        for x in &self.0 {
+            while other_iter.peek().is_some() && **other_iter.peek().unwrap() < *x {
+                other_iter.next();
+            }
+            if other_iter.peek().is_some() && *other_iter.peek().unwrap() == x {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The elements of `self` minus one occurrence per matching element of `other`.
+    #[allow(dead_code)] // Set-operation parity with `BTreeSet`'s `Sub`; not yet called in non-test code.
+    fn difference(self, other: Bag<T>) -> Bag<T> {
+        let mut difference = Vec::new();
+        let mut other_iter = other.0.into_iter().peekable();
+
+        for x in self.0 {
+            while other_iter.peek().is_some() && *other_iter.peek().unwrap() < x {
+                other_iter.next();
+            }
+            if other_iter.peek().is_some() && *other_iter.peek().unwrap() == x {
+                other_iter.next();
+            } else {
+                difference.push(x);
+            }
+        }
+
+        Bag(difference)
+    }
+
+    /// The elements that are in exactly one of `self` and `other` (each matched pair of equal
+    /// elements, one from either side, cancels out).
+    #[allow(dead_code)] // Set-operation parity with `BTreeSet`'s `BitXor`; not yet called in non-test code.
+    fn symmetric_difference(self, other: Bag<T>) -> Bag<T> {
+        let mut result = Vec::new();
+        let mut iter_1 = self.0.into_iter().peekable();
+        let mut iter_2 = other.0.into_iter().peekable();
+
+        loop {
+            match (iter_1.peek(), iter_2.peek()) {
+                (None, None) => break,
+                (Some(_), None) => result.push(iter_1.next().unwrap()),
+                (None, Some(_)) => result.push(iter_2.next().unwrap()),
+                (Some(x), Some(y)) if x < y => result.push(iter_1.next().unwrap()),
+                (Some(x), Some(y)) if x > y => result.push(iter_2.next().unwrap()),
+                (Some(_), Some(_)) => {
+                    iter_1.next();
+                    iter_2.next();
+                }
+            }
+        }
+
+        Bag(result)
+    }
+
+    /// Does any element appear more than once?
+    fn has_duplicate(&self) -> bool {
+        self.0.windows(2).any(|pair| pair[0] == pair[1])
+    }
+
+    /// How many times does `value` appear?
+    fn count(&self, value: &T) -> usize {
+        self.0.iter().filter(|elem| *elem == value).count()
+    }
 }
 
 #[test]
@@ -355,8 +618,15 @@ fn test_bag() {
     assert_eq!(show(bag("aabeeg").sum(bag("abbcf"))), "aaabbbceefg");
     assert_eq!(show(bag("aabeeg").union(bag("abbcf"))), "aabbceefg");
     assert_eq!(show(bag("abbcdff").intersection(bag("bceeffg"))), "bcff");
+    assert_eq!(show(bag("abbcdff").difference(bag("bceeffg"))), "abd");
+    assert_eq!(
+        show(bag("abbcdff").symmetric_difference(bag("bceeffg"))),
+        "abdeeg"
+    );
     assert!(bag("ace").is_subset(&bag("abccde")));
     assert!(!bag("ace").is_subset(&bag("abde")));
     assert!(bag("a").is_subset(&bag("aa")));
     assert!(bag("b").is_subset(&bag("abc")));
+    assert!(!bag("ab").has_duplicate());
+    assert!(bag("aab").has_duplicate());
 }
@@ -0,0 +1,455 @@
+use super::{Constraint, YesNoMaybe};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/************************
+ *       Sieve          *
+ ************************/
+
+/// A smallest-prime-factor sieve, precomputed once up to some maximum and shared (`Arc`) across
+/// every `Prime`/`Composite`/`Coprime` constraint built from it, instead of each one re-deriving
+/// primality by trial division from scratch on every check -- the Apples puzzle calls `is_prime`
+/// on sums in the thousands from inside a `Pred`, once per candidate, on every `simplify_table`
+/// pass, which adds up fast.
+#[derive(Debug)]
+pub struct Sieve {
+    /// `spf[n]` is the smallest prime factor of `n`, for `2 <= n <= max`. `spf[0]` and `spf[1]`
+    /// are unused placeholders so indices line up with the values they describe.
+    spf: Vec<i64>,
+}
+
+impl Sieve {
+    /// Build a sieve covering every value up to and including `max` -- e.g. the highest sum any
+    /// posted `Prime`/`Composite`/`Coprime` constraint's variables could reach. Callers share the
+    /// returned `Arc` across every constraint built from it, rather than each building their own.
+    pub fn new(max: i64) -> Arc<Sieve> {
+        assert!(max >= 0, "Sieve::new: max must be non-negative, got {}", max);
+        let max = max as usize;
+        let mut spf = vec![0i64; max + 1];
+        for p in 2..=max {
+            if spf[p] == 0 {
+                let mut m = p;
+                while m <= max {
+                    if spf[m] == 0 {
+                        spf[m] = p as i64;
+                    }
+                    m += p;
+                }
+            }
+        }
+        Arc::new(Sieve { spf })
+    }
+
+    /// `spf[n]`, panicking with a clear message instead of a raw index-out-of-bounds if `n`
+    /// exceeds the sieve's precomputed maximum -- that means it was built too small for the
+    /// constraints it's shared with.
+    fn spf(&self, n: i64) -> i64 {
+        *self.spf.get(n as usize).unwrap_or_else(|| {
+            panic!(
+                "Sieve: value {} is beyond this sieve's max of {}; build it with a larger Sieve::new",
+                n,
+                self.spf.len() - 1
+            )
+        })
+    }
+
+    fn is_prime(&self, n: i64) -> bool {
+        n >= 2 && self.spf(n) == n
+    }
+
+    fn is_composite(&self, n: i64) -> bool {
+        n >= 2 && self.spf(n) != n
+    }
+
+    /// `n.abs()`, saturating instead of panicking on `i64::MIN` (whose magnitude doesn't fit in an
+    /// `i64`) -- the saturated result is still certain to exceed any sieve actually built for a
+    /// real puzzle, so it falls through to `spf`'s own clear out-of-range panic instead of a
+    /// confusing arithmetic-overflow one.
+    fn saturating_abs(n: i64) -> i64 {
+        n.checked_abs().unwrap_or(i64::MAX)
+    }
+
+    /// Distinct prime factors of `n`, via the repeated-division walk using `spf`.
+    fn prime_factors(&self, n: i64) -> HashSet<i64> {
+        let mut n = Sieve::saturating_abs(n);
+        let mut factors = HashSet::new();
+        while n > 1 {
+            let p = self.spf(n);
+            factors.insert(p);
+            while n % p == 0 {
+                n /= p;
+            }
+        }
+        factors
+    }
+
+    /// Whether any/every value in `lo..=hi` satisfies `pred` (e.g. `Sieve::is_prime`). Shared by
+    /// `Prime`/`Composite::check`'s range-scan fallback, which only differ in which predicate
+    /// they scan for.
+    fn any_in(&self, lo: i64, hi: i64, pred: impl Fn(&Sieve, i64) -> bool) -> bool {
+        (lo.max(2)..=hi).any(|n| pred(self, n))
+    }
+
+    fn all_in(&self, lo: i64, hi: i64, pred: impl Fn(&Sieve, i64) -> bool) -> bool {
+        (lo..=hi).all(|n| pred(self, n))
+    }
+}
+
+/// Beyond this width, `Prime`/`Composite::check` gives up on scanning a still-undetermined range
+/// one value at a time and returns `Maybe` instead -- the "only on small determined ranges" half
+/// of the sieve fallback, so a huge unresolved range costs O(1), not O(range width), per check.
+const MAX_SCAN_WIDTH: i64 = 4096;
+
+/// The achievable-range arithmetic `Prime`/`Composite`/`Divisible` all need, in the same shape as
+/// `Linear`'s `singleton`/`and`/`or` (src/constraints/linear.rs): a one-point range, the sum of
+/// two ranges' sums, and their hull. Factored out so the three of them don't each hand-roll the
+/// same three methods around a `coeffs` field; `Linear` predates this file and keeps its own copy.
+fn sum_range_singleton<T: Into<i64>>(coeffs: &[i64], index: usize, elem: T) -> (i64, i64) {
+    let value = coeffs[index] * elem.into();
+    (value, value)
+}
+
+fn sum_range_and(a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sum_range_or(a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    (a.0.min(b.0), a.1.max(b.1))
+}
+
+/// Shared `check` body for `Prime`/`Composite`: both reduce to "is every/no value in this range
+/// prime (or composite)", differing only in which `Sieve` predicate they scan with.
+fn check_range_by(
+    sieve: &Sieve,
+    min: i64,
+    max: i64,
+    pred: impl Fn(&Sieve, i64) -> bool + Copy,
+) -> YesNoMaybe {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    if min == max {
+        return if pred(sieve, min) { Yes } else { No };
+    }
+    // `i128` so a huge `var_range` domain's span can't overflow on the way to this comparison.
+    if max as i128 - min as i128 + 1 > MAX_SCAN_WIDTH as i128 {
+        return Maybe;
+    }
+    if !sieve.any_in(min, max, pred) {
+        No
+    } else if sieve.all_in(min, max, pred) {
+        Yes
+    } else {
+        Maybe
+    }
+}
+
+/************************
+ *   Constraint: Prime  *
+ ************************/
+
+/// The sum `sum(coeff_i * x_i)` is prime. `Self::Set` is the same achievable-sum range as
+/// `Linear`. `check` decides a single value with one sieve lookup, and a range at most
+/// `MAX_SCAN_WIDTH` wide by scanning per-value sieve lookups over it, rejecting it when it
+/// contains no primes at all; on any wider range it returns `Maybe` without scanning.
+#[derive(Debug, Clone)]
+pub struct Prime {
+    coeffs: Vec<i64>,
+    sieve: Arc<Sieve>,
+}
+
+impl Prime {
+    pub fn new(coeffs: impl IntoIterator<Item = i64>, sieve: Arc<Sieve>) -> Prime {
+        Prime {
+            coeffs: coeffs.into_iter().collect(),
+            sieve,
+        }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone + Into<i64> + Send + Sync + Sized + 'static> Constraint<T>
+    for Prime
+{
+    type Set = (i64, i64);
+
+    const NAME: &'static str = "Prime";
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        sum_range_singleton(&self.coeffs, index, elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        sum_range_and(a, b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        sum_range_or(a, b)
+    }
+
+    fn check(&self, (min, max): Self::Set) -> YesNoMaybe {
+        check_range_by(&self.sieve, min, max, Sieve::is_prime)
+    }
+}
+
+/************************
+ * Constraint: Composite*
+ ************************/
+
+/// The sum `sum(coeff_i * x_i)` is composite (not prime, and greater than 1). Mirrors `Prime`
+/// exactly, down to the range-based early rejection, just with primality flipped.
+#[derive(Debug, Clone)]
+pub struct Composite {
+    coeffs: Vec<i64>,
+    sieve: Arc<Sieve>,
+}
+
+impl Composite {
+    pub fn new(coeffs: impl IntoIterator<Item = i64>, sieve: Arc<Sieve>) -> Composite {
+        Composite {
+            coeffs: coeffs.into_iter().collect(),
+            sieve,
+        }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone + Into<i64> + Send + Sync + Sized + 'static> Constraint<T>
+    for Composite
+{
+    type Set = (i64, i64);
+
+    const NAME: &'static str = "Composite";
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        sum_range_singleton(&self.coeffs, index, elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        sum_range_and(a, b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        sum_range_or(a, b)
+    }
+
+    fn check(&self, (min, max): Self::Set) -> YesNoMaybe {
+        check_range_by(&self.sieve, min, max, Sieve::is_composite)
+    }
+}
+
+/************************
+ *  Constraint: Divisible*
+ ************************/
+
+/// The sum `sum(coeff_i * x_i)` is divisible by `by`. Doesn't need the sieve -- divisibility by a
+/// fixed `by` is an O(1) arithmetic check on the range's endpoints, not a primality question -- but
+/// lives alongside `Prime`/`Composite`/`Coprime` since puzzles tend to want all four together.
+#[derive(Debug, Clone)]
+pub struct Divisible {
+    coeffs: Vec<i64>,
+    by: i64,
+}
+
+impl Divisible {
+    pub fn new(coeffs: impl IntoIterator<Item = i64>, by: i64) -> Divisible {
+        assert!(
+            by != 0 && by != i64::MIN,
+            "Divisible::new: by must be nonzero and representable as a positive i64, got {}",
+            by
+        );
+        Divisible {
+            coeffs: coeffs.into_iter().collect(),
+            by,
+        }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone + Into<i64> + Send + Sync + Sized + 'static> Constraint<T>
+    for Divisible
+{
+    type Set = (i64, i64);
+
+    const NAME: &'static str = "Divisible";
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        sum_range_singleton(&self.coeffs, index, elem)
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        sum_range_and(a, b)
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        sum_range_or(a, b)
+    }
+
+    fn check(&self, (min, max): Self::Set) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        let by = self.by.unsigned_abs() as i128;
+        // The smallest multiple of `by` that's >= min, computed in `i128` since `min` can be
+        // `i64::MIN` -- rounding it up to the next multiple would otherwise overflow `i64`.
+        let min = min as i128;
+        let first_multiple = min.div_euclid(by) * by + if min.rem_euclid(by) == 0 { 0 } else { by };
+        if first_multiple > max as i128 {
+            No
+        } else if min == max as i128 || by == 1 {
+            Yes
+        } else {
+            Maybe
+        }
+    }
+}
+
+/************************
+ *  Constraint: Coprime *
+ ************************/
+
+/// `gcd(a, b) == 1` for the two posted variables' raw values (not a sum of them, unlike
+/// `Prime`/`Composite`/`Divisible`): `Self::Set` tracks each operand's own determined value or
+/// `None`, the same "remember what's pinned down so far, forget what isn't" idiom `Pred` uses for
+/// its parameters, since coprimality isn't expressible as a property of a combined range the way
+/// primality-of-a-sum is.
+#[derive(Debug, Clone)]
+pub struct Coprime {
+    sieve: Arc<Sieve>,
+}
+
+impl Coprime {
+    pub fn new(sieve: Arc<Sieve>) -> Coprime {
+        Coprime { sieve }
+    }
+}
+
+impl<T: Debug + PartialEq + Clone + Into<i64> + Send + Sync + Sized + 'static> Constraint<T>
+    for Coprime
+{
+    type Set = [Option<i64>; 2];
+
+    const NAME: &'static str = "Coprime";
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        let mut set = [None, None];
+        set[index] = Some(elem.into());
+        set
+    }
+
+    /// Combine two params' worth of `Some`/`None` info. Unlike `Pred`'s `and` (which assumes its
+    /// two operands only ever disagree by one having `None` where the other has data, since the
+    /// solver always folds disjoint parameter indices together), this keeps a value only where
+    /// both sides that *do* have an opinion agree -- so it stays commutative even when both
+    /// operands pin down the same index.
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        let mut result = [None; 2];
+        for i in 0..2 {
+            result[i] = match (a[i], b[i]) {
+                (Some(x), None) | (None, Some(x)) => Some(x),
+                (Some(x), Some(y)) if x == y => Some(x),
+                _ => None,
+            };
+        }
+        result
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        let mut result = a;
+        for i in 0..2 {
+            if result[i] != b[i] {
+                result[i] = None;
+            }
+        }
+        result
+    }
+
+    fn check(&self, [a, b]: Self::Set) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        match (a, b) {
+            // `prime_factors` has nothing to say about 0 (it has every prime as a factor, not
+            // none): gcd(0, n) == |n|, so 0 is coprime only with +-1, and never with another 0.
+            (Some(0), Some(0)) => No,
+            (Some(0), Some(n)) | (Some(n), Some(0)) => {
+                if n == 1 || n == -1 {
+                    Yes
+                } else {
+                    No
+                }
+            }
+            (Some(a), Some(b)) => {
+                let shares_a_factor = self
+                    .sieve
+                    .prime_factors(a)
+                    .intersection(&self.sieve.prime_factors(b))
+                    .next()
+                    .is_some();
+                if shares_a_factor {
+                    No
+                } else {
+                    Yes
+                }
+            }
+            _ => Maybe,
+        }
+    }
+}
+
+#[test]
+fn test_prime() {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    let sieve = Sieve::new(20);
+    let prime = Prime::new([1], sieve);
+
+    assert_eq!(prime.singleton(0, 7), (7, 7));
+    assert_eq!(Constraint::<i32>::check(&prime, (7, 7)), Yes);
+    assert_eq!(Constraint::<i32>::check(&prime, (8, 8)), No);
+    assert_eq!(Constraint::<i32>::check(&prime, (1, 1)), No);
+    assert_eq!(Constraint::<i32>::check(&prime, (2, 3)), Yes); // every value in [2, 3] is prime
+    assert_eq!(Constraint::<i32>::check(&prime, (8, 10)), No); // no value in [8, 10] is prime
+    assert_eq!(Constraint::<i32>::check(&prime, (5, 8)), Maybe); // 5 and 7 are prime, 6 and 8 aren't
+}
+
+#[test]
+fn test_composite() {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    let sieve = Sieve::new(20);
+    let composite = Composite::new([1], sieve);
+
+    assert_eq!(Constraint::<i32>::check(&composite, (8, 8)), Yes);
+    assert_eq!(Constraint::<i32>::check(&composite, (7, 7)), No);
+    assert_eq!(Constraint::<i32>::check(&composite, (8, 9)), Yes); // 8 and 9 are both composite
+    assert_eq!(Constraint::<i32>::check(&composite, (2, 3)), No); // 2 and 3 are both prime
+    assert_eq!(Constraint::<i32>::check(&composite, (7, 9)), Maybe); // 7 is prime, 8 and 9 aren't
+}
+
+#[test]
+fn test_divisible() {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    let div_by_5 = Divisible::new([1], 5);
+    assert_eq!(Constraint::<i32>::check(&div_by_5, (10, 10)), Yes);
+    assert_eq!(Constraint::<i32>::check(&div_by_5, (11, 11)), No);
+    assert_eq!(Constraint::<i32>::check(&div_by_5, (11, 14)), No); // no multiple of 5 in [11, 14]
+    assert_eq!(Constraint::<i32>::check(&div_by_5, (10, 14)), Maybe); // 10 is divisible, 11..14 aren't
+
+    let div_by_1 = Divisible::new([1], 1);
+    assert_eq!(Constraint::<i32>::check(&div_by_1, (10, 14)), Yes); // every integer is divisible by 1
+}
+
+#[test]
+fn test_coprime() {
+    use YesNoMaybe::{Maybe, No, Yes};
+
+    let sieve = Sieve::new(40);
+    let coprime = Coprime::new(sieve);
+
+    assert_eq!(coprime.singleton(0, 6), [Some(6), None]);
+    assert_eq!(
+        Constraint::<i32>::and(&coprime, coprime.singleton(0, 6), coprime.singleton(1, 35)),
+        [Some(6), Some(35)]
+    );
+
+    assert_eq!(Constraint::<i32>::check(&coprime, [Some(6), Some(35)]), Yes); // 6 = 2*3, 35 = 5*7
+    assert_eq!(Constraint::<i32>::check(&coprime, [Some(6), Some(15)]), No); // both divisible by 3
+    assert_eq!(Constraint::<i32>::check(&coprime, [Some(6), None]), Maybe);
+}
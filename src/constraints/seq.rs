@@ -1,9 +1,11 @@
 use super::{Constraint, YesNoMaybe};
 use bitvec::{bitvec, vec::BitVec};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs;
 use std::hash::Hash;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
 /// The constraint that `{X1, ..., Xn}` is a word from a list of allowed words. Or more generally,
 /// that that sequence is present in a list of allowed sequences.
@@ -14,13 +16,32 @@ pub struct Seq<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'stati
     allowed_seqs: Vec<Vec<T>>,
 }
 
+/// `word_list_file`'s cache, keyed by `(path, word_len)` so a dictionary is only read and
+/// filtered once per word length, however many repeat calls ask for that pair; a new length
+/// reads the file again.
+fn word_list_cache() -> &'static Mutex<HashMap<(PathBuf, usize), Seq<char>>> {
+    static CACHE: OnceLock<Mutex<HashMap<(PathBuf, usize), Seq<char>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
 impl Seq<char> {
-    /// Allowed sequences are the words of the given length from the file at `path`.
+    /// Allowed sequences are the words of the given length from the file at `path`. Cached by
+    /// `(path, word_len)`, so the file is only read and filtered the first time a given pair is
+    /// requested.
     pub fn word_list_file(
         path: impl AsRef<Path>,
         word_len: usize,
     ) -> Result<Seq<char>, std::io::Error> {
-        let word_list = fs::read_to_string(path)?;
+        let path = path.as_ref().to_path_buf();
+        let key = (path, word_len);
+
+        let mut cache = word_list_cache().lock().unwrap();
+        if let Some(seq) = cache.get(&key) {
+            return Ok(seq.clone());
+        }
+
+        let (path, word_len) = key;
+        let word_list = fs::read_to_string(&path)?;
         let allowed_words = word_list
             .lines()
             .map(|s| s.trim())
@@ -31,10 +52,12 @@ impl Seq<char> {
         for word in &allowed_words {
             assert_eq!(word.len(), word_len);
         }
-        Ok(Seq {
+        let seq = Seq {
             seq_len: word_len,
             allowed_seqs: allowed_words,
-        })
+        };
+        cache.insert((path, word_len), seq.clone());
+        Ok(seq)
     }
 }
 
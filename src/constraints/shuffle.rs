@@ -0,0 +1,144 @@
+use super::multiset::Multiset;
+use super::{Constraint, YesNoMaybe};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/**********************
+ * Constraint: Shuffle *
+ **********************/
+
+/// The constraint that two disjoint groups of variables hold the same multiset of values: one
+/// group is some rearrangement of the other, without fixing what that multiset has to be. Takes
+/// `2 * half_len` parameters, the first `half_len` forming one group and the rest the other.
+///
+/// Where `Permutation` checks a group against a predetermined expected bag, `Shuffle` only
+/// relates two groups to each other — useful for puzzles where two rows/regions must contain
+/// identical contents, without saying in advance what that content is.
+#[derive(Debug, Clone)]
+pub struct Shuffle<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> {
+    half_len: usize,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Shuffle<T> {
+    pub fn new(half_len: usize) -> Shuffle<T> {
+        Shuffle {
+            half_len,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Debug + Hash + Eq + Ord + Clone + Sized + Send + Sync + 'static> Constraint<T>
+    for Shuffle<T>
+{
+    const NAME: &'static str = "Shuffle";
+
+    type Set = ShuffleSet<T>;
+
+    fn singleton(&self, index: usize, elem: T) -> Self::Set {
+        if index < self.half_len {
+            ShuffleSet {
+                left: MultisetRange::singleton(elem),
+                right: MultisetRange::unconstrained(),
+            }
+        } else {
+            ShuffleSet {
+                left: MultisetRange::unconstrained(),
+                right: MultisetRange::singleton(elem),
+            }
+        }
+    }
+
+    fn and(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        ShuffleSet {
+            left: a.left.and(b.left),
+            right: a.right.and(b.right),
+        }
+    }
+
+    fn or(&self, a: Self::Set, b: Self::Set) -> Self::Set {
+        ShuffleSet {
+            left: a.left.or(b.left),
+            right: a.right.or(b.right),
+        }
+    }
+
+    fn check(&self, set: Self::Set) -> YesNoMaybe {
+        set.left.matches(&set.right)
+    }
+}
+
+/************************
+ *     ShuffleSet       *
+ ************************/
+
+/// `Shuffle`'s `Set`: the range of multisets each group could still resolve to, tracked
+/// independently, since `and`/`or` fold over every parameter regardless of which group it's in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShuffleSet<T: Ord> {
+    left: MultisetRange<T>,
+    right: MultisetRange<T>,
+}
+
+/// The range `[min, max]` a group's multiset could still resolve to: `min` is what's guaranteed
+/// present no matter how its undecided variables resolve, `max` is everything still possible.
+/// `unconstrained()` (both empty) is the identity for `and`, standing in for "no parameter from
+/// this group has been folded in yet".
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MultisetRange<T: Ord> {
+    min: Multiset<T>,
+    max: Multiset<T>,
+}
+
+impl<T: Ord + Clone> MultisetRange<T> {
+    fn unconstrained() -> MultisetRange<T> {
+        MultisetRange {
+            min: Multiset::empty(),
+            max: Multiset::empty(),
+        }
+    }
+
+    fn singleton(elem: T) -> MultisetRange<T> {
+        MultisetRange {
+            min: Multiset::singleton(elem.clone()),
+            max: Multiset::singleton(elem),
+        }
+    }
+
+    fn and(self, other: MultisetRange<T>) -> MultisetRange<T> {
+        MultisetRange {
+            min: self.min.sum(other.min),
+            max: self.max.sum(other.max),
+        }
+    }
+
+    fn or(self, other: MultisetRange<T>) -> MultisetRange<T> {
+        MultisetRange {
+            min: self.min.intersection(other.min),
+            max: self.max.union(other.max),
+        }
+    }
+
+    fn is_decided(&self) -> bool {
+        self.min == self.max
+    }
+
+    /// `Yes` iff both groups are fully decided and equal, `No` iff some value's guaranteed count
+    /// on one side already exceeds the most that could ever appear on the other, `Maybe`
+    /// otherwise.
+    fn matches(&self, other: &MultisetRange<T>) -> YesNoMaybe {
+        use YesNoMaybe::{Maybe, No, Yes};
+
+        if self.is_decided() && other.is_decided() {
+            return if self.min == other.min { Yes } else { No };
+        }
+        let impossible = self.min.keys().any(|v| self.min.count(v) > other.max.count(v))
+            || other.min.keys().any(|v| other.min.count(v) > self.max.count(v));
+        if impossible {
+            No
+        } else {
+            Maybe
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! A testing utility for checking that a `Constraint` implementation is internally consistent.
+//! Writing a correct `Constraint` is error-prone: a buggy `or` can silently drop solutions during
+//! `simplify_table`. `verify_constraint` brute-forces a small candidate space and checks the
+//! invariants that `singleton`/`and`/`or`/`check` must jointly satisfy.
+
+use super::{Constraint, YesNoMaybe};
+use std::fmt::Debug;
+
+/// Check the core invariants of a `Constraint` implementation over `num_params` parameters, each
+/// ranging over `candidates`. Panics with a description of the failing property if the
+/// constraint is inconsistent.
+///
+/// This brute-forces every assignment in `candidates^num_params`, so keep both small (it's a test
+/// helper, not a solver). Apply it to your own `Constraint` impls as a `#[test]`, the way the
+/// tests below apply it to `Pred`, `Count`, `GlobalCardinality`, and the other built-in
+/// constraints.
+pub fn verify_constraint<C, T>(constraint: &C, num_params: usize, candidates: &[T])
+where
+    C: Constraint<T>,
+    C::Set: Clone + PartialEq + Debug,
+    T: Debug + PartialEq + Clone,
+{
+    assert!(num_params > 0, "verify_constraint: need at least one parameter");
+    assert!(
+        !candidates.is_empty(),
+        "verify_constraint: need at least one candidate value"
+    );
+
+    check_and_or_are_commutative_and_associative(constraint, num_params, candidates);
+    check_full_assignments_are_decided(constraint, num_params, candidates);
+    check_oracle_property(constraint, num_params, candidates);
+}
+
+/// `and` and `or` must be commutative and associative, since `eval_constraint_for_all` folds them
+/// in an order that depends only on the parameters' positions in the table, not in some
+/// constraint-specific canonical order. `and` only ever combines different parameters there, so
+/// it is checked on singletons at distinct parameter indices (as many as `num_params` allows);
+/// `or` combines alternative values of one parameter, so it is checked within index 0.
+fn check_and_or_are_commutative_and_associative<C, T>(
+    constraint: &C,
+    num_params: usize,
+    candidates: &[T],
+) where
+    C: Constraint<T>,
+    C::Set: Clone + PartialEq + Debug,
+    T: Debug + PartialEq + Clone,
+{
+    for a in candidates {
+        let sa = constraint.singleton(0, a.clone());
+        for b in candidates {
+            let sb = constraint.singleton(0, b.clone());
+            if num_params >= 2 {
+                let sb1 = constraint.singleton(1, b.clone());
+                assert_eq!(
+                    constraint.and(sa.clone(), sb1.clone()),
+                    constraint.and(sb1.clone(), sa.clone()),
+                    "{}::and is not commutative on singleton(0, {:?}), singleton(1, {:?})",
+                    C::NAME,
+                    a,
+                    b
+                );
+            }
+            assert_eq!(
+                constraint.or(sa.clone(), sb.clone()),
+                constraint.or(sb.clone(), sa.clone()),
+                "{}::or is not commutative on singleton({:?}), singleton({:?})",
+                C::NAME,
+                a,
+                b
+            );
+            for c in candidates {
+                let sc = constraint.singleton(0, c.clone());
+                if num_params >= 3 {
+                    let sb1 = constraint.singleton(1, b.clone());
+                    let sc2 = constraint.singleton(2, c.clone());
+                    assert_eq!(
+                        constraint.and(constraint.and(sa.clone(), sb1.clone()), sc2.clone()),
+                        constraint.and(sa.clone(), constraint.and(sb1.clone(), sc2.clone())),
+                        "{}::and is not associative on {:?}, {:?}, {:?}",
+                        C::NAME,
+                        a,
+                        b,
+                        c
+                    );
+                }
+                assert_eq!(
+                    constraint.or(constraint.or(sa.clone(), sb.clone()), sc.clone()),
+                    constraint.or(sa.clone(), constraint.or(sb.clone(), sc.clone())),
+                    "{}::or is not associative on {:?}, {:?}, {:?}",
+                    C::NAME,
+                    a,
+                    b,
+                    c
+                );
+            }
+        }
+    }
+}
+
+/// `check` of a fully-determined assignment (every parameter pinned to a singleton) must be
+/// decided one way or the other; `Maybe` would mean the constraint can't even evaluate a concrete
+/// board, which `eval_constraint_for_all` relies on not happening.
+fn check_full_assignments_are_decided<C, T>(constraint: &C, num_params: usize, candidates: &[T])
+where
+    C: Constraint<T>,
+    C::Set: Clone,
+    T: Debug + PartialEq + Clone,
+{
+    for assignment in all_assignments(num_params, candidates) {
+        assert_ne!(
+            constraint.check(fold_assignment(constraint, &assignment)),
+            YesNoMaybe::Maybe,
+            "{}::check returned Maybe for the fully-determined assignment {:?}",
+            C::NAME,
+            assignment
+        );
+    }
+}
+
+/// The oracle property: folding `singleton`+`or` over each parameter's whole candidate set, then
+/// `and`-ing the parameters together (exactly as `eval_constraint_for_all` does), must agree with
+/// brute-force enumeration of every concrete assignment: `No` iff none satisfy, `Yes` iff all do,
+/// `Maybe` otherwise.
+fn check_oracle_property<C, T>(constraint: &C, num_params: usize, candidates: &[T])
+where
+    C: Constraint<T>,
+    C::Set: Clone,
+    T: Debug + PartialEq + Clone,
+{
+    let mut whole_space = fold_column(constraint, 0, candidates);
+    for param_index in 1..num_params {
+        whole_space = constraint.and(whole_space, fold_column(constraint, param_index, candidates));
+    }
+
+    let num_satisfying = all_assignments(num_params, candidates)
+        .filter(|assignment| {
+            constraint.check(fold_assignment(constraint, assignment)) == YesNoMaybe::Yes
+        })
+        .count();
+    let num_total = candidates.len().pow(num_params as u32);
+
+    let expected = if num_satisfying == 0 {
+        YesNoMaybe::No
+    } else if num_satisfying == num_total {
+        YesNoMaybe::Yes
+    } else {
+        YesNoMaybe::Maybe
+    };
+    assert_eq!(
+        constraint.check(whole_space),
+        expected,
+        "{}::check disagreed with brute-force enumeration over {:?}^{} \
+         ({} of {} assignments satisfy it)",
+        C::NAME,
+        candidates,
+        num_params,
+        num_satisfying,
+        num_total
+    );
+}
+
+/// `singleton(index, c_0) or singleton(index, c_1) or ...` over every candidate.
+fn fold_column<C, T>(constraint: &C, param_index: usize, candidates: &[T]) -> C::Set
+where
+    C: Constraint<T>,
+    T: Clone,
+{
+    let mut candidates = candidates.iter();
+    let mut set = constraint.singleton(param_index, candidates.next().unwrap().clone());
+    for candidate in candidates {
+        set = constraint.or(set, constraint.singleton(param_index, candidate.clone()));
+    }
+    set
+}
+
+/// `singleton(0, assignment[0]) and singleton(1, assignment[1]) and ...`.
+fn fold_assignment<C, T>(constraint: &C, assignment: &[T]) -> C::Set
+where
+    C: Constraint<T>,
+    T: Clone,
+{
+    let mut values = assignment.iter();
+    let mut set = constraint.singleton(0, values.next().unwrap().clone());
+    for (index, value) in values.enumerate() {
+        set = constraint.and(set, constraint.singleton(index + 1, value.clone()));
+    }
+    set
+}
+
+/// Every assignment in `candidates^num_params`, in lexicographic order.
+fn all_assignments<T: Clone>(
+    num_params: usize,
+    candidates: &[T],
+) -> impl Iterator<Item = Vec<T>> + '_ {
+    let total = candidates.len().pow(num_params as u32);
+    (0..total).map(move |mut index| {
+        // Decode `index` in base `candidates.len()`, one digit per parameter.
+        let mut assignment = Vec::with_capacity(num_params);
+        for _ in 0..num_params {
+            assignment.push(candidates[index % candidates.len()].clone());
+            index /= candidates.len();
+        }
+        assignment
+    })
+}
+
+#[test]
+fn test_verify_pred() {
+    use super::Pred;
+
+    verify_constraint(&Pred::new(|[a, b]: &[i32; 2]| a < b), 2, &[1, 2, 3]);
+    verify_constraint(&Pred::new(|[a]: &[i32; 1]| *a == 2), 1, &[1, 2, 3]);
+}
+
+#[test]
+fn test_verify_count() {
+    use super::Count;
+
+    verify_constraint(&Count::new(1, 1..=2), 2, &[1i32, 2, 3]);
+}
+
+#[test]
+fn test_verify_global_cardinality() {
+    use super::GlobalCardinality;
+
+    verify_constraint(
+        &GlobalCardinality::new([("a", 1, 2), ("b", 0, 1)]),
+        2,
+        &["a", "b"],
+    );
+}
+
+#[test]
+fn test_verify_linear() {
+    use super::Linear;
+
+    verify_constraint(&Linear::new([1, 1], 4, 4), 2, &[1i32, 2, 3]);
+    verify_constraint(&Linear::new([1, -1], 0, 1), 2, &[1i32, 2, 3]);
+}
+
+#[test]
+fn test_verify_formula() {
+    use super::{Expr, Formula};
+
+    verify_constraint(
+        &Formula::new(2, Expr::var(0).add(Expr::var(1)).eq(Expr::int(4))),
+        2,
+        &[1i32, 2, 3],
+    );
+    verify_constraint(
+        &Formula::new(2, Expr::var(0).lt(Expr::var(1))),
+        2,
+        &[1i32, 2, 3],
+    );
+}
+
+#[test]
+fn test_verify_shuffle() {
+    use super::Shuffle;
+
+    verify_constraint(&Shuffle::new(1), 2, &[1i32, 2, 3]);
+    verify_constraint(&Shuffle::new(2), 4, &[1i32, 2]);
+}
+
+#[test]
+fn test_verify_all_different() {
+    use super::AllDifferent;
+
+    verify_constraint(&AllDifferent::new(), 2, &[1i32, 2, 3]);
+    verify_constraint(&AllDifferent::new(), 3, &[1i32, 2, 3]);
+}
+
+#[test]
+fn test_verify_prime() {
+    use super::{Prime, Sieve};
+
+    // Candidates chosen so the achievable-sum range's endpoints agree with membership at every
+    // integer in between (see `Prime`'s doc comment) -- 2 and 3 are the only two
+    // consecutive integers that are both prime, so the interval hull is exact here.
+    verify_constraint(&Prime::new([1], Sieve::new(10)), 1, &[2i32, 3]);
+}
+
+#[test]
+fn test_verify_composite() {
+    use super::{Composite, Sieve};
+
+    verify_constraint(&Composite::new([1], Sieve::new(10)), 1, &[8i32, 9]);
+}
+
+#[test]
+fn test_verify_divisible() {
+    use super::Divisible;
+
+    // `by = 1`: every integer is divisible, so the interval hull is trivially exact regardless of
+    // which candidates are picked.
+    verify_constraint(&Divisible::new([1], 1), 1, &[5i32, 6, 7]);
+}
+
+#[test]
+fn test_verify_coprime() {
+    use super::{Coprime, Sieve};
+
+    verify_constraint(&Coprime::new(Sieve::new(40)), 2, &[2i32, 3, 6]);
+}
+
+#[test]
+fn test_verify_disjoint() {
+    use super::Disjoint;
+
+    verify_constraint(&Disjoint::new([1, 2]), 2, &[1i32, 2, 3]);
+}
+
+#[test]
+fn test_verify_distinct() {
+    use super::Distinct;
+
+    verify_constraint(&Distinct::new(), 2, &[1i32, 2, 3]);
+    verify_constraint(&Distinct::new(), 3, &[1i32, 2, 3]);
+}
@@ -0,0 +1,444 @@
+//! `Domain<V>` is the representation of one variable's remaining candidate values in a `Table`.
+//!
+//! Most value types use `Domain::Vec`, a plain list, same as before this module existed. Value
+//! types that opt in via `State::value_index` (small, densely-indexable types like Sudoku digits
+//! or booleans) get `Domain::Bitset` instead: membership, removal, and size become word
+//! operations on a `u128`, and `Table::make_guess` becomes a single set-bit write, instead of
+//! allocating and scanning a `Vec` on every `simplify_table` pass. Large contiguous integer
+//! domains (e.g. `Solvomatic::var_range`'s `4000..5000`) get `Domain::Range` instead, which keeps
+//! just the endpoints rather than ever materializing the values in between.
+
+/// One variable's remaining candidate values.
+#[derive(Debug, Clone)]
+pub enum Domain<V> {
+    Vec(Vec<V>),
+    Bitset { bits: u128, universe: Vec<V> },
+    Range { set: RangeSet, to_value: fn(i64) -> V },
+}
+
+impl<V: Clone> Domain<V> {
+    /// Build a domain for `values`. Uses the bitset representation when `bitset_index` maps every
+    /// value to an index under 128 (so it fits in one `u128` word); equal values share a bit, and
+    /// indices no value maps to are simply never set. Falls back to the plain `Vec` representation
+    /// when any value has no index or an index of 128 or more.
+    pub fn new(values: Vec<V>, bitset_index: impl Fn(&V) -> Option<usize>) -> Domain<V> {
+        match try_bitset(&values, &bitset_index) {
+            Some(domain) => domain,
+            None => Domain::Vec(values),
+        }
+    }
+
+    /// Build a domain for the inclusive range `lo..=hi`, kept symbolic as a pair of endpoints
+    /// instead of enumerated up front. `to_value` reconstructs a `V` from a position in the
+    /// range; see `Solvomatic::var_range`.
+    pub fn new_range(lo: i64, hi: i64, to_value: fn(i64) -> V) -> Domain<V> {
+        Domain::Range {
+            set: RangeSet::new(lo, hi),
+            to_value,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Domain::Vec(values) => values.len(),
+            Domain::Bitset { bits, .. } => bits.count_ones() as usize,
+            Domain::Range { set, .. } => set.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The single remaining value, if there's exactly one.
+    pub fn only(&self) -> Option<V> {
+        match self.len() {
+            1 => self.iter().next(),
+            _ => None,
+        }
+    }
+
+    /// Feed a canonical, order-independent representation of this domain's currently-present
+    /// values into `hasher`, for `Table::fingerprint`'s transposition cache. `Vec`/`Bitset` sort
+    /// and hash the actual values, same as before `Range` existed; `Range` hashes its endpoints
+    /// directly instead of enumerating every value in between, so a fingerprint stays cheap no
+    /// matter how wide the range is.
+    pub fn hash_canonical<H: std::hash::Hasher>(&self, hasher: &mut H)
+    where
+        V: Ord + std::hash::Hash,
+    {
+        use std::hash::Hash;
+
+        match self {
+            Domain::Range { set, .. } => set.hash(hasher),
+            Domain::Vec(values) => {
+                let mut values = values.iter().collect::<Vec<_>>();
+                values.sort();
+                values.hash(hasher);
+            }
+            Domain::Bitset { bits, universe } => {
+                // Bits are already visited in increasing index order, and no two set bits hold
+                // the same value (see `try_bitset`), so this is already canonical without sorting.
+                // Hashed as a `Vec` (not value-by-value) so the length is mixed in too, the same
+                // as the `Vec`/`Range` arms above -- otherwise two differently-shaped columns
+                // could hash to the same flat sequence of values.
+                let values = present_bits(*bits, universe.len())
+                    .map(|i| &universe[i])
+                    .collect::<Vec<_>>();
+                values.hash(hasher);
+            }
+        }
+    }
+
+    /// Every currently-present value, in increasing order for `Range`/`Bitset`, insertion order
+    /// for `Vec`. Yields owned values rather than references, since `Range` has nowhere to borrow
+    /// a `V` from — it only ever stores endpoints.
+    pub fn iter(&self) -> DomainIter<'_, V> {
+        match self {
+            Domain::Vec(values) => DomainIter::Vec(values.iter()),
+            Domain::Bitset { bits, universe } => DomainIter::Bitset {
+                bits: *bits,
+                universe,
+                next: 0,
+            },
+            Domain::Range { set, to_value } => DomainIter::Range {
+                set_iter: set.iter(),
+                to_value: *to_value,
+            },
+        }
+    }
+
+    /// Remove the `index`-th *currently present* value, matching `Vec::remove`'s semantics (the
+    /// index is among the values still present, not a position in some underlying universe).
+    pub fn remove(&mut self, index: usize) {
+        match self {
+            Domain::Vec(values) => {
+                values.remove(index);
+            }
+            Domain::Bitset { bits, universe } => {
+                let bit = present_bits(*bits, universe.len())
+                    .nth(index)
+                    .expect("Domain::remove: index out of range");
+                *bits &= !(1u128 << bit);
+            }
+            Domain::Range { set, .. } => set.remove_nth(index),
+        }
+    }
+
+    /// Split a `Range` domain into two halves at the midpoint of its currently-present values, so
+    /// `Table::guess` can binary-search a huge range (see `Solvomatic::var_range`) instead of
+    /// branching into one child table per remaining value. Panics on anything but a multi-element
+    /// `Range` domain -- `Vec`/`Bitset` domains are never wide enough for enumerating them
+    /// per-value to be a problem, so `guess` only calls this on `Range`.
+    pub fn bisect(&self) -> (Domain<V>, Domain<V>) {
+        match self {
+            Domain::Range { set, to_value } => {
+                let (lo, hi) = set.bisect();
+                (
+                    Domain::Range {
+                        set: lo,
+                        to_value: *to_value,
+                    },
+                    Domain::Range {
+                        set: hi,
+                        to_value: *to_value,
+                    },
+                )
+            }
+            _ => panic!("Domain::bisect only supports the Range representation"),
+        }
+    }
+
+    /// Collapse to the single, `index`-th currently present value (as in `Table::make_guess`).
+    pub fn make_guess(&mut self, index: usize) {
+        match self {
+            Domain::Vec(values) => {
+                let value = values.swap_remove(index);
+                *values = vec![value];
+            }
+            Domain::Bitset { bits, universe } => {
+                let bit = present_bits(*bits, universe.len())
+                    .nth(index)
+                    .expect("Domain::make_guess: index out of range");
+                *bits = 1u128 << bit;
+            }
+            Domain::Range { set, .. } => set.make_guess_nth(index),
+        }
+    }
+}
+
+fn present_bits(bits: u128, universe_len: usize) -> impl Iterator<Item = usize> {
+    (0..universe_len).filter(move |i| bits & (1u128 << i) != 0)
+}
+
+fn try_bitset<V: Clone>(
+    values: &[V],
+    bitset_index: &impl Fn(&V) -> Option<usize>,
+) -> Option<Domain<V>> {
+    let indices = values.iter().map(bitset_index).collect::<Option<Vec<_>>>()?;
+    let universe_len = *indices.iter().max()? + 1;
+    if universe_len > 128 {
+        return None;
+    }
+
+    let mut universe: Vec<Option<V>> = vec![None; universe_len];
+    let mut bits = 0u128;
+    for (value, index) in values.iter().zip(&indices) {
+        universe[*index] = Some(value.clone());
+        bits |= 1u128 << index;
+    }
+    // Slots no value maps to never get their bit set, and `iter`/`only` only read slots whose bit
+    // is set, so any value serves as a placeholder there.
+    let placeholder = values.first()?.clone();
+    let universe = universe
+        .into_iter()
+        .map(|value| value.unwrap_or_else(|| placeholder.clone()))
+        .collect::<Vec<_>>();
+    Some(Domain::Bitset { bits, universe })
+}
+
+pub enum DomainIter<'a, V> {
+    Vec(std::slice::Iter<'a, V>),
+    Bitset {
+        bits: u128,
+        universe: &'a [V],
+        next: usize,
+    },
+    Range {
+        set_iter: RangeSetIter<'a>,
+        to_value: fn(i64) -> V,
+    },
+}
+
+impl<'a, V: Clone> Iterator for DomainIter<'a, V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<V> {
+        match self {
+            DomainIter::Vec(iter) => iter.next().cloned(),
+            DomainIter::Bitset {
+                bits,
+                universe,
+                next,
+            } => {
+                while *next < universe.len() {
+                    let i = *next;
+                    *next += 1;
+                    if *bits & (1u128 << i) != 0 {
+                        return Some(universe[i].clone());
+                    }
+                }
+                None
+            }
+            DomainIter::Range { set_iter, to_value } => set_iter.next().map(|n| to_value(n)),
+        }
+    }
+}
+
+/************************
+ *     RangeSet         *
+ ************************/
+
+/// `hi - lo + 1` computed in `i128` so endpoints near `i64`'s bounds (plausible for a range whose
+/// whole point is to avoid enumerating huge domains) can't overflow on the way to a `usize`.
+fn range_width(lo: i64, hi: i64) -> usize {
+    (hi as i128 - lo as i128 + 1) as usize
+}
+
+/// A set of `i64`s kept as a sorted list of disjoint, non-adjacent inclusive ranges, the same way
+/// `Domain::Bitset` keeps a small value set as a word instead of a `Vec`: membership is implicit
+/// in the endpoints, so a million-wide range costs the same two `i64`s as a ten-wide one, right up
+/// until something actually punches a hole in it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet(Vec<(i64, i64)>);
+
+impl RangeSet {
+    fn new(lo: i64, hi: i64) -> RangeSet {
+        assert!(lo <= hi, "RangeSet::new: empty range {}..={}", lo, hi);
+        RangeSet(vec![(lo, hi)])
+    }
+
+    fn len(&self) -> usize {
+        self.0
+            .iter()
+            .map(|&(lo, hi)| range_width(lo, hi))
+            .sum()
+    }
+
+    fn iter(&self) -> RangeSetIter<'_> {
+        RangeSetIter {
+            ranges: self.0.iter(),
+            current: None,
+        }
+    }
+
+    /// The `index`-th currently-present value, in increasing order.
+    fn nth(&self, index: usize) -> i64 {
+        let mut remaining = index;
+        for &(lo, hi) in &self.0 {
+            let width = range_width(lo, hi);
+            if remaining < width {
+                return lo + remaining as i64;
+            }
+            remaining -= width;
+        }
+        panic!("RangeSet::nth: index out of range");
+    }
+
+    /// Remove the `index`-th currently-present value, splitting its range in two if the value is
+    /// interior to it.
+    fn remove_nth(&mut self, index: usize) {
+        let value = self.nth(index);
+        let range_index = self
+            .0
+            .iter()
+            .position(|&(lo, hi)| lo <= value && value <= hi)
+            .unwrap();
+        let (lo, hi) = self.0[range_index];
+        if lo == hi {
+            self.0.remove(range_index);
+        } else if value == lo {
+            self.0[range_index] = (lo + 1, hi);
+        } else if value == hi {
+            self.0[range_index] = (lo, hi - 1);
+        } else {
+            self.0[range_index] = (lo, value - 1);
+            self.0.insert(range_index + 1, (value + 1, hi));
+        }
+    }
+
+    /// Collapse to just the `index`-th currently-present value.
+    fn make_guess_nth(&mut self, index: usize) {
+        let value = self.nth(index);
+        self.0 = vec![(value, value)];
+    }
+
+    /// Split into two sets at the midpoint of this set's currently-present values: everything
+    /// before the midpoint, and everything from the midpoint on. Used by `Table::guess` to binary
+    /// search a huge range instead of branching into one child table per remaining value (see
+    /// `Domain::bisect`). Panics on a set with fewer than two values, same as `Domain::new_range`
+    /// panicking on an empty range -- there'd be nothing left to split.
+    fn bisect(&self) -> (RangeSet, RangeSet) {
+        let half = self.len() / 2;
+        assert!(half > 0, "RangeSet::bisect: fewer than two values to split");
+        let mut lo_ranges = Vec::new();
+        let mut hi_ranges = Vec::new();
+        let mut seen = 0;
+        for &(lo, hi) in &self.0 {
+            let width = range_width(lo, hi);
+            if seen + width <= half {
+                lo_ranges.push((lo, hi));
+            } else if seen >= half {
+                hi_ranges.push((lo, hi));
+            } else {
+                let split = lo + (half - seen) as i64;
+                lo_ranges.push((lo, split - 1));
+                hi_ranges.push((split, hi));
+            }
+            seen += width;
+        }
+        (RangeSet(lo_ranges), RangeSet(hi_ranges))
+    }
+}
+
+pub struct RangeSetIter<'a> {
+    ranges: std::slice::Iter<'a, (i64, i64)>,
+    /// `(next value to yield, upper bound of the range in progress)`, or `None` between ranges.
+    current: Option<(i64, i64)>,
+}
+
+impl<'a> Iterator for RangeSetIter<'a> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        loop {
+            if let Some((next, hi)) = self.current {
+                if next <= hi {
+                    self.current = Some((next + 1, hi));
+                    return Some(next);
+                }
+                self.current = None;
+            }
+            let &(lo, hi) = self.ranges.next()?;
+            self.current = Some((lo, hi));
+        }
+    }
+}
+
+#[test]
+fn test_range_set() {
+    let mut set = RangeSet::new(10, 15);
+    assert_eq!(set.len(), 6);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![10, 11, 12, 13, 14, 15]);
+    assert_eq!(set.nth(0), 10);
+    assert_eq!(set.nth(5), 15);
+
+    // Removing an interior value splits the range in two.
+    set.remove_nth(2); // removes 12
+    assert_eq!(set.len(), 5);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![10, 11, 13, 14, 15]);
+
+    // Removing an endpoint just shrinks the range.
+    set.remove_nth(0); // removes 10
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![11, 13, 14, 15]);
+
+    set.make_guess_nth(1); // collapse to the 2nd present value, 13
+    assert_eq!(set.len(), 1);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![13]);
+}
+
+#[test]
+fn test_bitset_domain() {
+    let mut domain = Domain::new(vec![3, 1, 4, 1, 5], |v: &i32| Some(*v as usize));
+    assert!(matches!(domain, Domain::Bitset { .. }));
+    assert_eq!(domain.len(), 4); // 1 is deduped since it maps to the same bit twice
+    let mut values = domain.iter().collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, vec![1, 3, 4, 5]);
+
+    domain.remove(0); // removes the lowest-indexed present value, 1
+    let mut values = domain.iter().collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, vec![3, 4, 5]);
+
+    domain.make_guess(1); // collapse to the 2nd present value, 4
+    assert_eq!(domain.only(), Some(4));
+}
+
+#[test]
+fn test_range_domain() {
+    let mut domain: Domain<i32> = Domain::new_range(4000, 4002, |n| n as i32);
+    assert!(matches!(domain, Domain::Range { .. }));
+    assert_eq!(domain.len(), 3);
+    assert_eq!(domain.iter().collect::<Vec<_>>(), vec![4000, 4001, 4002]);
+
+    domain.remove(1); // removes the 2nd present value, 4001
+    assert_eq!(domain.iter().collect::<Vec<_>>(), vec![4000, 4002]);
+
+    domain.make_guess(1); // collapse to the 2nd present value, 4002
+    assert_eq!(domain.only(), Some(4002));
+}
+
+#[test]
+fn test_range_domain_bisect() {
+    let domain: Domain<i32> = Domain::new_range(1, 1_000_000, |n| n as i32);
+    let (lo, hi) = domain.bisect();
+    assert_eq!(lo.len() + hi.len(), domain.len());
+    assert_eq!(lo.iter().last(), Some(500_000));
+    assert_eq!(hi.iter().next(), Some(500_001));
+
+    // Bisecting still works after a hole has been punched in the middle of the range.
+    let mut domain: Domain<i32> = Domain::new_range(1, 10, |n| n as i32);
+    domain.remove(4); // removes the 5th present value, 5
+    let (lo, hi) = domain.bisect();
+    assert_eq!(lo.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(hi.iter().collect::<Vec<_>>(), vec![6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn test_vec_domain_fallback() {
+    // No bitset_index provided (the default on `State`) means every value falls back to `Vec`.
+    let domain = Domain::new(vec!['a', 'b', 'c'], |_: &char| None);
+    assert!(matches!(domain, Domain::Vec(_)));
+    assert_eq!(domain.len(), 3);
+}
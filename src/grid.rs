@@ -0,0 +1,151 @@
+//! Reusable `State` implementations for grid-shaped puzzles (Sudoku, magic squares, word grids,
+//! ...), so games that just fill in a 2-D or 1-D array of cells don't need to hand-roll their own
+//! `State`, `Display`, and variable-group bookkeeping every time. See `Grid` and `Line`.
+
+use crate::state::State;
+use std::fmt;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/************************
+ *        Grid          *
+ ************************/
+
+/// A `W`-wide, `H`-tall grid of optional values, addressed by `(row, col)` with `row` in `0..H`
+/// and `col` in `0..W`. Implements `State` directly — `solver.var((row, col), ...)` each cell —
+/// and its associated functions (`rows`, `cols`, `block`) build the variable groups Sudoku-style
+/// puzzles constrain, so callers don't write those loops out by hand.
+///
+/// The `MetaData` is a cell formatter, `fn(Option<&V>) -> String`, since how an empty cell prints
+/// ("_", ".", "   _") is the one thing that's genuinely puzzle-specific about displaying a grid.
+#[derive(Debug)]
+pub struct Grid<const W: usize, const H: usize, V> {
+    cells: Vec<Vec<Option<V>>>,
+    format_cell: fn(Option<&V>) -> String,
+}
+
+impl<const W: usize, const H: usize, V: Debug + Hash + Eq + Ord + Clone + Send + Sync + 'static>
+    Grid<W, H, V>
+{
+    /// Every `(row, col)` variable in the grid, in row-major order.
+    pub fn cells() -> Vec<(usize, usize)> {
+        (0..H)
+            .flat_map(|row| (0..W).map(move |col| (row, col)))
+            .collect()
+    }
+
+    /// Each row as its own variable group, e.g. for a `Permutation` constraint.
+    pub fn rows() -> Vec<Vec<(usize, usize)>> {
+        (0..H)
+            .map(|row| (0..W).map(|col| (row, col)).collect())
+            .collect()
+    }
+
+    /// Each column as its own variable group.
+    pub fn cols() -> Vec<Vec<(usize, usize)>> {
+        (0..W)
+            .map(|col| (0..H).map(|row| (row, col)).collect())
+            .collect()
+    }
+
+    /// The `block_h x block_w` block at block-grid position `(block_row, block_col)`, spanning
+    /// rows `block_row*block_h .. (block_row+1)*block_h` and columns
+    /// `block_col*block_w .. (block_col+1)*block_w`. Used for Sudoku's 3x3 boxes.
+    pub fn block(
+        block_row: usize,
+        block_col: usize,
+        block_h: usize,
+        block_w: usize,
+    ) -> Vec<(usize, usize)> {
+        let mut cells = Vec::with_capacity(block_h * block_w);
+        for row in block_row * block_h..(block_row + 1) * block_h {
+            for col in block_col * block_w..(block_col + 1) * block_w {
+                cells.push((row, col));
+            }
+        }
+        cells
+    }
+}
+
+impl<const W: usize, const H: usize, V: Debug + Hash + Eq + Ord + Clone + Send + Sync + 'static>
+    State for Grid<W, H, V>
+{
+    type Var = (usize, usize);
+    type Value = V;
+    type MetaData = fn(Option<&V>) -> String;
+
+    fn new(format_cell: &Self::MetaData) -> Grid<W, H, V> {
+        Grid {
+            cells: vec![vec![None; W]; H],
+            format_cell: *format_cell,
+        }
+    }
+
+    fn set(&mut self, var: (usize, usize), val: V) {
+        let (row, col) = var;
+        self.cells[row][col] = Some(val);
+    }
+}
+
+impl<const W: usize, const H: usize, V: Debug + Hash + Eq + Ord + Clone + Send + Sync + 'static>
+    fmt::Display for Grid<W, H, V>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.cells {
+            for cell in row {
+                write!(f, "{}", (self.format_cell)(cell.as_ref()))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/************************
+ *        Line          *
+ ************************/
+
+/// A 1-D analogue of `Grid`: `N` optional values addressed by a plain `usize` index. For puzzles
+/// like palindromes or word-ladders that fill in a single row of cells rather than a 2-D grid.
+#[derive(Debug)]
+pub struct Line<const N: usize, V> {
+    cells: Vec<Option<V>>,
+    format_cell: fn(Option<&V>) -> String,
+}
+
+impl<const N: usize, V: Debug + Hash + Eq + Ord + Clone + Send + Sync + 'static> Line<N, V> {
+    /// Every variable in the line, in order.
+    pub fn cells() -> Vec<usize> {
+        (0..N).collect()
+    }
+}
+
+impl<const N: usize, V: Debug + Hash + Eq + Ord + Clone + Send + Sync + 'static> State
+    for Line<N, V>
+{
+    type Var = usize;
+    type Value = V;
+    type MetaData = fn(Option<&V>) -> String;
+
+    fn new(format_cell: &Self::MetaData) -> Line<N, V> {
+        Line {
+            cells: vec![None; N],
+            format_cell: *format_cell,
+        }
+    }
+
+    fn set(&mut self, var: usize, val: V) {
+        self.cells[var] = Some(val);
+    }
+}
+
+impl<const N: usize, V: Debug + Hash + Eq + Ord + Clone + Send + Sync + 'static> fmt::Display
+    for Line<N, V>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for cell in &self.cells {
+            write!(f, "{}", (self.format_cell)(cell.as_ref()))?;
+        }
+        Ok(())
+    }
+}
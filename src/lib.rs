@@ -331,27 +331,269 @@
 // how the code currently works.
 #![allow(clippy::result_unit_err)]
 
+mod domain;
+mod grid;
 mod state;
 mod table;
 
 pub mod constraints;
+pub mod sat;
+pub mod sudoku;
+
+pub use grid::{Grid, Line};
 pub use state::{State, StateSet};
 
 use constraints::{Constraint, YesNoMaybe};
-use std::mem;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::time::Instant;
 use table::{EntryIndex, Table, VarIndex};
 
+/************************
+ *     Simplification   *
+ ************************/
+
+/// Stats accumulated while simplifying a single table, merged into the solver's running `Stats`
+/// by the caller. Kept as a plain local struct (rather than touching shared state directly) so
+/// that `solve_parallel`'s workers can merge it into atomics without `simplify_table_impl` itself
+/// needing to know whether it's running sequentially or in parallel.
+#[derive(Default)]
+struct SimplifyDelta {
+    propagation_rounds: u64,
+    entries_deleted: u64,
+    variables_solved_by_propagation: u64,
+}
+
+/// The propagate-to-fixpoint core of `simplify_table`, shared by the sequential and parallel
+/// solve paths. Returns the simplified table, or `Err` with its trail if propagation emptied one
+/// of its domains (so the caller can record it as a no-good).
+///
+/// If `propagate` is `false` (see `Config::propagate`), the arc-consistency deletion loop below is
+/// skipped entirely: constraints are still marked dead once satisfied, and a table on which a live
+/// constraint already evaluates to `No` is rejected as a conflict, but no candidate values are
+/// ruled out ahead of `Table::guess`, so the search falls back to pure blind branching. This is
+/// mainly useful for comparing propagation's effect on `Stats::guesses` on a given puzzle.
+fn simplify_table_impl<S: State>(
+    constraints: &[DynConstraint<S>],
+    log_completed: bool,
+    propagate: bool,
+    mut table: Table<S>,
+) -> (Result<Table<S>, Vec<(VarIndex, S::Value)>>, SimplifyDelta) {
+    use YesNoMaybe::{No, Yes};
+
+    let mut delta = SimplifyDelta::default();
+
+    table.init_live_constraints(constraints.len());
+
+    for (i, constraint) in constraints.iter().enumerate() {
+        if table.is_constraint_live(i) && (constraint.is_satisfied)(&table) == Yes {
+            if log_completed {
+                eprintln!(
+                    "Completed constraint {} on {:?}",
+                    constraint.name, constraint.params
+                );
+            }
+            table.kill_constraint(i);
+        }
+    }
+
+    if !propagate {
+        let violated = constraints.iter().enumerate().any(|(i, constraint)| {
+            table.is_constraint_live(i) && (constraint.is_satisfied)(&table) == No
+        });
+        if violated {
+            return (Err(table.trail), delta);
+        }
+        return (Ok(table), delta);
+    }
+
+    loop {
+        delta.propagation_rounds += 1;
+        let mut to_delete: Vec<(VarIndex, EntryIndex)> = Vec::new();
+        for (i, constraint) in constraints.iter().enumerate() {
+            if !table.is_constraint_live(i) {
+                continue;
+            }
+            for param_index in 0..constraint.params.len() {
+                let answers = (constraint.eval)(&table, param_index);
+                for (entry, answer) in answers.into_iter().enumerate() {
+                    if answer == No {
+                        let var = &constraint.params[param_index];
+                        let var_index = table.vars.iter().position(|v| v == var).unwrap();
+                        let key = (var_index, entry);
+                        if !to_delete.contains(&key) {
+                            to_delete.push(key);
+                        }
+                    }
+                }
+            }
+        }
+        if to_delete.is_empty() {
+            break;
+        }
+        to_delete.sort();
+        delta.entries_deleted += to_delete.len() as u64;
+        for (var, entry) in to_delete.iter().rev() {
+            let was_singleton = table.entries[*var].len() == 1;
+            table.entries[*var].remove(*entry);
+            if table.entries[*var].is_empty() {
+                return (Err(table.trail), delta);
+            }
+            if !was_singleton && table.entries[*var].len() == 1 {
+                delta.variables_solved_by_propagation += 1;
+            }
+        }
+    }
+    (Ok(table), delta)
+}
+
+/// Record `trail` as a forbidden combination of branch decisions, so that any future table whose
+/// trail repeats it can be discarded without re-exploring it.
+fn record_no_good<S: State>(
+    no_goods: &mut HashMap<VarIndex, Vec<Vec<(VarIndex, S::Value)>>>,
+    trail: Vec<(VarIndex, S::Value)>,
+) {
+    let Some(min_var) = trail.iter().map(|(var, _)| *var).min() else {
+        return;
+    };
+    no_goods.entry(min_var).or_default().push(trail);
+}
+
+/// Does `table`'s trail repeat a previously learned no-good?
+fn is_no_good<S: State>(
+    no_goods: &HashMap<VarIndex, Vec<Vec<(VarIndex, S::Value)>>>,
+    table: &Table<S>,
+) -> bool {
+    let decided: HashSet<(VarIndex, S::Value)> = table.trail.iter().cloned().collect();
+    table.trail.iter().any(|(var, _)| match no_goods.get(var) {
+        Some(no_goods) => no_goods
+            .iter()
+            .any(|no_good| no_good.iter().all(|d| decided.contains(d))),
+        None => false,
+    })
+}
+
+/// The `solve_parallel` counterpart to `Solvomatic::already_seen`, sharing `seen_tables` across
+/// workers behind a mutex.
+fn already_seen<S: State>(dedupe_tables: bool, seen_tables: &Mutex<HashSet<u64>>, table: &Table<S>) -> bool {
+    if !dedupe_tables {
+        return false;
+    }
+    !seen_tables.lock().unwrap().insert(table.fingerprint())
+}
+
+/// Restrict `table`'s domain for `var_index` down to just its `entry_index`-th remaining value,
+/// in place. Like `Solvomatic::assume`, but operating directly on a `Table` by position rather
+/// than by looking up a `Var`/`Value` pair; used by `Solvomatic::to_cnf` to pin one parameter at a
+/// time while enumerating a constraint's candidate assignments.
+fn restrict_to_entry<S: State>(table: &mut Table<S>, var_index: VarIndex, entry_index: EntryIndex) {
+    let to_remove = (0..table.entries[var_index].len())
+        .filter(|&i| i != entry_index)
+        .collect::<Vec<_>>();
+    for i in to_remove.into_iter().rev() {
+        table.entries[var_index].remove(i);
+    }
+}
+
+/// The read-only context threaded through `solve_fork_join_branch`'s recursion: everything that
+/// doesn't change branch to branch, bundled up so the recursive calls don't need a dozen separate
+/// parameters.
+struct ForkJoinCtx<'a, S: State> {
+    constraints: &'a [DynConstraint<S>],
+    log_completed: bool,
+    propagate: bool,
+    dedupe_tables: bool,
+    fork_join_depth: usize,
+    metadata: &'a S::MetaData,
+    solutions: &'a Mutex<Vec<S>>,
+    no_goods: &'a Mutex<HashMap<VarIndex, Vec<Vec<(VarIndex, S::Value)>>>>,
+    stats: &'a Mutex<Stats>,
+    seen_tables: &'a Mutex<HashSet<u64>>,
+}
+
+/// Simplify `table`, then either record it as a solution/no-good, or `guess()` it and recurse into
+/// its children: fanned out via `rayon::par_iter` while `depth < ctx.fork_join_depth`, and plain
+/// sequential recursion beyond that (see `Config::fork_join_depth`).
+fn solve_fork_join_branch<S: State>(ctx: &ForkJoinCtx<S>, table: Table<S>, depth: usize) {
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    let (result, delta) = simplify_table_impl(ctx.constraints, ctx.log_completed, ctx.propagate, table);
+    {
+        let mut stats = ctx.stats.lock().unwrap();
+        stats.tables_popped += 1;
+        stats.propagation_rounds += delta.propagation_rounds;
+        stats.entries_deleted += delta.entries_deleted;
+        stats.variables_solved_by_propagation += delta.variables_solved_by_propagation;
+    }
+
+    let table = match result {
+        Err(trail) => {
+            ctx.stats.lock().unwrap().conflicts += 1;
+            record_no_good::<S>(&mut ctx.no_goods.lock().unwrap(), trail);
+            return;
+        }
+        Ok(table) => table,
+    };
+
+    if table.is_solved() {
+        ctx.solutions.lock().unwrap().push(table.to_state(ctx.metadata));
+        return;
+    }
+
+    ctx.stats.lock().unwrap().guesses += 1;
+    let children = table
+        .guess()
+        .into_iter()
+        .filter(|child| {
+            !is_no_good(&ctx.no_goods.lock().unwrap(), child)
+                && !already_seen(ctx.dedupe_tables, ctx.seen_tables, child)
+        })
+        .collect::<Vec<_>>();
+
+    if depth < ctx.fork_join_depth {
+        children
+            .into_par_iter()
+            .for_each(|child| solve_fork_join_branch(ctx, child, depth + 1));
+    } else {
+        for child in children {
+            solve_fork_join_branch(ctx, child, depth + 1);
+        }
+    }
+}
+
+/// Every combination of entry indices across `var_indices`' domains in `entries`, i.e. the
+/// cartesian product of their remaining candidate values.
+fn entry_index_combinations<S: State>(
+    var_indices: &[VarIndex],
+    entries: &[crate::domain::Domain<S::Value>],
+) -> Vec<Vec<EntryIndex>> {
+    var_indices.iter().fold(vec![Vec::new()], |partials, &var_index| {
+        let domain_len = entries[var_index].len();
+        partials
+            .into_iter()
+            .flat_map(|partial| {
+                (0..domain_len).map(move |entry_index| {
+                    let mut partial = partial.clone();
+                    partial.push(entry_index);
+                    partial
+                })
+            })
+            .collect()
+    })
+}
+
 /************************
  *     DynConstraint    *
  ************************/
 
 struct DynConstraint<S: State> {
-    // TODO
-    #[allow(unused)]
     name: String,
     params: Vec<S::Var>,
     eval: Box<dyn Fn(&Table<S>, usize) -> Vec<YesNoMaybe> + Send + Sync + 'static>,
+    /// Whole-table version of `eval`, used to notice once this constraint is fully satisfied so
+    /// it can be retired (see `Table::live_constraints`).
+    is_satisfied: Box<dyn Fn(&Table<S>) -> YesNoMaybe + Send + Sync + 'static>,
 }
 
 impl<S: State> DynConstraint<S> {
@@ -361,12 +603,25 @@ impl<S: State> DynConstraint<S> {
     ) -> DynConstraint<S> {
         let name = C::NAME.to_owned();
         let params = params.into_iter().collect::<Vec<_>>();
+        let constraint = std::sync::Arc::new(constraint);
 
         let params_copy = params.clone();
+        let eval_constraint = constraint.clone();
         let eval = Box::new(move |table: &Table<S>, param_index: usize| {
-            table.eval_constraint_for_all(&constraint, &params_copy, param_index)
+            table.eval_constraint_for_all(&*eval_constraint, &params_copy, param_index)
+        });
+
+        let params_copy = params.clone();
+        let is_satisfied = Box::new(move |table: &Table<S>| {
+            table.eval_constraint(&*constraint, &params_copy, None)
         });
-        DynConstraint { name, params, eval }
+
+        DynConstraint {
+            name,
+            params,
+            eval,
+            is_satisfied,
+        }
     }
 }
 
@@ -375,11 +630,30 @@ impl<S: State> DynConstraint<S> {
  ************************/
 
 pub struct Solvomatic<S: State> {
-    tables: Vec<Table<S>>,
-    solutions: Vec<S>,
+    /// The current base table: has every `var()`'s column, and is progressively narrowed by
+    /// `assume()` and simplified against `constraints` by `add_constraint_incremental()`. Never
+    /// branched in place; `solve()`/`solutions()` seed their worklist from a clone of it, so a
+    /// retained, already-propagated `root` can be re-solved from after tightening the problem
+    /// instead of re-deriving everything from the raw `var()` domains.
+    root: Table<S>,
+    tables: VecDeque<Table<S>>,
     constraints: Vec<DynConstraint<S>>,
     metadata: S::MetaData,
     config: Config,
+    stats: Stats,
+    /// Learned no-goods: forbidden combinations of (var, value) branch decisions, each recorded
+    /// the moment a table's trail turned out unsatisfiable. Indexed by the no-good's
+    /// lowest-numbered var, so `is_no_good` only has to check the no-goods that could possibly
+    /// apply to a given trail. See the `trail` field on `Table`.
+    no_goods: HashMap<VarIndex, Vec<Vec<(VarIndex, S::Value)>>>,
+    /// Set once solving has used `root` for the first time (a `solve()`/`solutions()` call, or an
+    /// `assume()`/`add_constraint_incremental()` call). `var()` panics afterward, since adding a
+    /// new column to an already-propagated table isn't supported.
+    started: bool,
+    /// Fingerprints (see `Table::fingerprint`) of every guessed table seen so far, when
+    /// `Config::dedupe_tables` is enabled. Two different guess orders can reach byte-for-byte
+    /// identical tables; this lets the search skip re-exploring one it's already seen.
+    seen_tables: HashSet<u64>,
 }
 
 impl<S: State> Solvomatic<S> {
@@ -387,25 +661,61 @@ impl<S: State> Solvomatic<S> {
     /// constraints, then `solve()` to solve for them.
     pub fn new(metadata: S::MetaData) -> Solvomatic<S> {
         Solvomatic {
-            tables: vec![Table::new()],
-            solutions: Vec::new(),
+            root: Table::new(),
+            tables: VecDeque::new(),
             constraints: Vec::new(),
             config: Config::default(),
+            stats: Stats::default(),
+            no_goods: HashMap::new(),
+            started: false,
+            seen_tables: HashSet::new(),
             metadata,
         }
     }
 
+    /// Bookkeeping accumulated so far by `solve()`/`solutions()`: tables popped, guesses made,
+    /// propagation rounds, entries deleted, conflicts, peak worklist size, and elapsed time.
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
     pub fn config(&mut self) -> &mut Config {
         &mut self.config
     }
 
     /// Add a new variable, with a set of possible values.
     pub fn var(&mut self, var: S::Var, values: impl IntoIterator<Item = S::Value>) {
-        assert_eq!(self.tables.len(), 1, "Called 'var' after solving started");
-        self.tables[0].add_column(var, values);
+        assert!(!self.started, "Called 'var' after solving started");
+        self.root.add_column(var, values);
+    }
+
+    /// Add a new variable whose domain is the inclusive range `range`, kept symbolic as a pair of
+    /// endpoints (see `domain::Domain::Range`) instead of enumerated into a `Vec` up front. Useful
+    /// for variables with huge ranges, like `solver.var_range("apple", 4000..=5999, |n| n as i32)`,
+    /// where `var` would otherwise have to allocate and store every candidate value. `from_i64`
+    /// reconstructs a `S::Value` from a position in the range.
+    ///
+    /// `Table::guess` bisects a `Range` column instead of branching into one child table per
+    /// remaining value, so narrowing a huge range down to a single value only takes
+    /// `log2(width)` guesses. Constraint evaluation is still per-value, though (see
+    /// `Table::eval_constraint_for_param`): there's currently no `Sum`/`Linear`-style fast path
+    /// that reads a `Range` column's endpoints directly instead of folding `singleton`/`or` over
+    /// every value in it, so a constraint touching a wide range is checked one candidate value
+    /// at a time even though the range itself is never materialized into a `Vec`.
+    pub fn var_range(
+        &mut self,
+        var: S::Var,
+        range: std::ops::RangeInclusive<i64>,
+        from_i64: fn(i64) -> S::Value,
+    ) {
+        assert!(!self.started, "Called 'var_range' after solving started");
+        self.root
+            .add_range_column(var, *range.start(), *range.end(), from_i64);
     }
 
-    /// Add the requirement that the variables `params` must obey `constraint`.
+    /// Add the requirement that the variables `params` must obey `constraint`. Only takes effect
+    /// the next time `root` gets (re-)simplified, i.e. the next `solve()`/`solutions()` call, or
+    /// sooner if paired with `add_constraint_incremental()`.
     pub fn constraint<C: Constraint<S::Value>>(
         &mut self,
         params: impl IntoIterator<Item = S::Var>,
@@ -420,61 +730,128 @@ impl<S: State> Solvomatic<S> {
             .push(DynConstraint::new(params, constraint));
     }
 
-    fn simplify_table(&self, mut table: Table<S>) -> Option<Table<S>> {
-        use YesNoMaybe::No;
+    /// Like `constraint()`, but also immediately simplifies the retained `root` table against it,
+    /// so the restriction is folded into `root` right away instead of waiting for the next
+    /// `solve()`/`solutions()` call. Returns `Err(())` if this makes the problem unsatisfiable.
+    pub fn add_constraint_incremental<C: Constraint<S::Value>>(
+        &mut self,
+        params: impl IntoIterator<Item = S::Var>,
+        constraint: C,
+    ) -> Result<(), ()> {
+        self.constraint(params, constraint);
+        self.started = true;
+        self.simplify_root()
+    }
 
-        // TODO: Delete completed constraints, and log them
-        // if self.config.log_completed {
-        //     eprintln!"(completed constraint {} {:?}",
-        //         constraint.name, constraint.params
-        //     )
-        // }
-        /*
-        let mut relevant_constraints = Vec::new();
-        for constraint in &self.constraints {
-            match (constraint.eval)(&table, None) {
-                // Constraint is always satisfied, we can ignore it
-                Yes => (),
-                Maybe => relevant_constraints.push(constraint),
-                No => return None,
-            }
+    /// Post `make(subset)` as a `constraint()` for every `k`-element subset of `vars`, in
+    /// lexicographic order of their indices into `vars`. Subsets are generated on the fly (see
+    /// `Combinations`), so this stays memory-bounded even for large `vars` as long as `k` is
+    /// small. Handy for "this predicate must hold for every pair/triple of these variables"
+    /// instead of writing out one `constraint()` call per combination by hand.
+    pub fn constraint_over_combinations<C: Constraint<S::Value>>(
+        &mut self,
+        vars: &[S::Var],
+        k: usize,
+        make: impl Fn(&[S::Var]) -> C,
+    ) {
+        for subset in Combinations::new(vars, k) {
+            let constraint = make(&subset);
+            self.constraint(subset, constraint);
         }
-        */
-
-        loop {
-            let mut to_delete: Vec<(VarIndex, EntryIndex)> = Vec::new();
-            // for var in 0..table.vars.len() {
-            //     if table.entries[var].len() == 1 {
-            //         continue;
-            //     }
-            for constraint in &self.constraints {
-                for param_index in 0..constraint.params.len() {
-                    let answers = (constraint.eval)(&table, param_index);
-                    for (entry, answer) in answers.into_iter().enumerate() {
-                        if answer == No {
-                            let var = &constraint.params[param_index];
-                            let var_index = table.vars.iter().position(|v| v == var).unwrap();
-                            let key = (var_index, entry);
-                            if !to_delete.contains(&key) {
-                                to_delete.push(key);
-                            }
-                        }
-                    }
-                }
-            }
-            //}
-            if to_delete.is_empty() {
-                break;
+    }
+
+    /// `constraint_over_combinations(vars, 2, make)`.
+    pub fn constraint_over_pairs<C: Constraint<S::Value>>(
+        &mut self,
+        vars: &[S::Var],
+        make: impl Fn(&[S::Var]) -> C,
+    ) {
+        self.constraint_over_combinations(vars, 2, make);
+    }
+
+    /// Permanently restrict `var`'s domain in the retained `root` table to just `value`, then
+    /// propagate. This is the "preset" workflow: solve once, inspect the result, then fix one
+    /// variable's answer and solve again, reusing the (mostly still valid) propagated root
+    /// instead of rebuilding every domain from scratch via `var()`. Returns `Err(())` if `value`
+    /// wasn't in `var`'s remaining domain, or if assuming it makes the problem unsatisfiable.
+    pub fn assume(&mut self, var: S::Var, value: S::Value) -> Result<(), ()> {
+        let var_index = self
+            .root
+            .vars
+            .iter()
+            .position(|v| *v == var)
+            .unwrap_or_else(|| panic!("Solvomatic::assume: unknown variable {:?}", var));
+
+        if !self.root.entries[var_index].iter().any(|v| v == value) {
+            return Err(());
+        }
+        let to_remove = self.root.entries[var_index]
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| *v != value)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        for i in to_remove.into_iter().rev() {
+            self.root.entries[var_index].remove(i);
+        }
+
+        self.started = true;
+        self.simplify_root()
+    }
+
+    /// Re-simplify `root` in place against the current `constraints`. Used by `assume()` and
+    /// `add_constraint_incremental()` to fold a new restriction into the retained root table right
+    /// away, rather than leaving it for the next `solve()`/`solutions()` call to discover.
+    fn simplify_root(&mut self) -> Result<(), ()> {
+        let root = std::mem::replace(&mut self.root, Table::new());
+        match self.simplify_table(root) {
+            Some(table) => {
+                self.root = table;
+                Ok(())
             }
-            to_delete.sort();
-            for (var, entry) in to_delete.iter().rev() {
-                table.entries[*var].remove(*entry);
-                if table.entries[*var].is_empty() {
-                    return None;
-                }
+            None => Err(()),
+        }
+    }
+
+    /// Seed a fresh worklist from `root` for `solve()`/`solutions()` to search from.
+    fn begin_search(&mut self) -> VecDeque<Table<S>> {
+        self.started = true;
+        VecDeque::from(vec![self.root.clone()])
+    }
+
+    fn simplify_table(&mut self, table: Table<S>) -> Option<Table<S>> {
+        let (result, delta) = simplify_table_impl(
+            &self.constraints,
+            self.config.log_completed,
+            self.config.propagate,
+            table,
+        );
+        self.stats.propagation_rounds += delta.propagation_rounds;
+        self.stats.entries_deleted += delta.entries_deleted;
+        self.stats.variables_solved_by_propagation += delta.variables_solved_by_propagation;
+        match result {
+            Ok(table) => Some(table),
+            Err(trail) => {
+                self.stats.conflicts += 1;
+                record_no_good(&mut self.no_goods, trail);
+                None
             }
         }
-        Some(table)
+    }
+
+    /// Does `table`'s trail repeat a previously learned no-good?
+    fn is_no_good(&self, table: &Table<S>) -> bool {
+        is_no_good(&self.no_goods, table)
+    }
+
+    /// Has an identical table (by `Table::fingerprint`) already been explored? Always `false`
+    /// when `Config::dedupe_tables` is off. Records `table` as seen as a side effect, so this
+    /// must only be called once per candidate branch.
+    fn already_seen(&mut self, table: &Table<S>) -> bool {
+        if !self.config.dedupe_tables {
+            return false;
+        }
+        !self.seen_tables.insert(table.fingerprint())
     }
 
     fn size(&self) -> usize {
@@ -485,48 +862,374 @@ impl<S: State> Solvomatic<S> {
         self.tables.iter().map(|table| table.possibilities()).sum()
     }
 
+    /// Pop the next table off the worklist, in the order dictated by `Config::search_mode`.
+    fn pop_table(&mut self) -> Option<Table<S>> {
+        match self.config.search_mode {
+            SearchMode::DepthFirst => self.tables.pop_back(),
+            SearchMode::BreadthFirst => self.tables.pop_front(),
+        }
+    }
+
+    /// Run the solver to completion and collect every solution.
+    ///
+    /// For just the first solution, or to stop after some number of them, use `solutions()`
+    /// instead: `solve` always drives the worklist to exhaustion before returning anything.
+    ///
+    /// If `Config::threads` is greater than 1, the worklist is distributed across that many
+    /// rayon worker threads instead of being walked by a single lazy `Solutions` iterator. This
+    /// can give a near-linear speedup on branch-heavy puzzles, but unlike `solutions()`, it can't
+    /// stop early: it always runs to exhaustion.
     pub fn solve(&mut self) -> StateSet<S> {
-        let start_time = Instant::now();
+        if self.config.threads <= 1 {
+            StateSet(self.solutions().collect())
+        } else {
+            let start_time = Instant::now();
+            let solutions = match self.config.parallel_strategy {
+                ParallelStrategy::WorkQueue => self.solve_parallel(),
+                ParallelStrategy::ForkJoin => self.solve_fork_join(),
+            };
+            self.stats.elapsed += start_time.elapsed();
+            solutions
+        }
+    }
+
+    /// The `Config::threads > 1` path for `solve()`. Workers share a mutex-guarded queue seeded
+    /// from `root` by `begin_search`, each repeatedly popping a table, simplifying it, and either recording
+    /// a solution or pushing its `guess()` children back onto the queue. `pending` counts tables
+    /// that are queued or being worked on; a worker only stops once it finds the queue empty and
+    /// `pending` at zero, meaning there's truly nothing left, not just a momentary lull.
+    fn solve_parallel(&mut self) -> StateSet<S> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.config.threads)
+            .build()
+            .expect("Solvomatic: failed to build rayon thread pool");
+
+        let queue = Mutex::new(self.begin_search());
+        let pending = AtomicUsize::new(queue.lock().unwrap().len());
+        let solutions: Mutex<Vec<S>> = Mutex::new(Vec::new());
+        let no_goods = Mutex::new(std::mem::take(&mut self.no_goods));
+        let stats = Mutex::new(std::mem::take(&mut self.stats));
+        let seen_tables = Mutex::new(std::mem::take(&mut self.seen_tables));
+
+        let constraints = &self.constraints;
+        let log_completed = self.config.log_completed;
+        let propagate = self.config.propagate;
+        let dedupe_tables = self.config.dedupe_tables;
+        let metadata = &self.metadata;
+
+        pool.scope(|scope| {
+            for _ in 0..self.config.threads {
+                scope.spawn(|_| loop {
+                    let table = queue.lock().unwrap().pop_back();
+                    let Some(table) = table else {
+                        if pending.load(Ordering::SeqCst) == 0 {
+                            break;
+                        }
+                        std::thread::yield_now();
+                        continue;
+                    };
+
+                    let (result, delta) = simplify_table_impl(constraints, log_completed, propagate, table);
+                    {
+                        let mut stats = stats.lock().unwrap();
+                        stats.tables_popped += 1;
+                        stats.propagation_rounds += delta.propagation_rounds;
+                        stats.entries_deleted += delta.entries_deleted;
+                        stats.variables_solved_by_propagation += delta.variables_solved_by_propagation;
+                    }
+
+                    match result {
+                        Err(trail) => {
+                            stats.lock().unwrap().conflicts += 1;
+                            record_no_good::<S>(&mut no_goods.lock().unwrap(), trail);
+                        }
+                        Ok(table) if table.is_solved() => {
+                            solutions.lock().unwrap().push(table.to_state(metadata));
+                        }
+                        Ok(table) => {
+                            stats.lock().unwrap().guesses += 1;
+                            let children = table.guess();
+                            let guarded_no_goods = no_goods.lock().unwrap();
+                            let mut guarded_queue = queue.lock().unwrap();
+                            let mut added = 0;
+                            for child in children {
+                                if !is_no_good(&guarded_no_goods, &child)
+                                    && !already_seen(dedupe_tables, &seen_tables, &child)
+                                {
+                                    guarded_queue.push_back(child);
+                                    added += 1;
+                                }
+                            }
+                            drop(guarded_queue);
+                            drop(guarded_no_goods);
+                            pending.fetch_add(added, Ordering::SeqCst);
+                        }
+                    }
+                    pending.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        self.no_goods = no_goods.into_inner().unwrap();
+        self.stats = stats.into_inner().unwrap();
+        self.seen_tables = seen_tables.into_inner().unwrap();
+        StateSet(solutions.into_inner().unwrap())
+    }
+
+    /// The `Config::parallel_strategy == ForkJoin` path for `solve()`: recursively fork each
+    /// branch's `guess()` children as separate rayon tasks via `par_iter`, rather than sharing one
+    /// queue across workers. Forking stops at `Config::fork_join_depth`, below which a subtree is
+    /// finished off by plain sequential recursion, so deep, narrow parts of the tree don't pay
+    /// task-spawning overhead for little benefit.
+    fn solve_fork_join(&mut self) -> StateSet<S> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.config.threads)
+            .build()
+            .expect("Solvomatic: failed to build rayon thread pool");
+
+        let solutions: Mutex<Vec<S>> = Mutex::new(Vec::new());
+        let no_goods = Mutex::new(std::mem::take(&mut self.no_goods));
+        let stats = Mutex::new(std::mem::take(&mut self.stats));
+        let seen_tables = Mutex::new(std::mem::take(&mut self.seen_tables));
+
+        let root = self.begin_search().pop_front().unwrap();
+        let ctx = ForkJoinCtx {
+            constraints: &self.constraints,
+            log_completed: self.config.log_completed,
+            propagate: self.config.propagate,
+            dedupe_tables: self.config.dedupe_tables,
+            fork_join_depth: self.config.fork_join_depth,
+            metadata: &self.metadata,
+            solutions: &solutions,
+            no_goods: &no_goods,
+            stats: &stats,
+            seen_tables: &seen_tables,
+        };
+
+        pool.install(|| solve_fork_join_branch(&ctx, root, 0));
+
+        self.no_goods = no_goods.into_inner().unwrap();
+        self.stats = stats.into_inner().unwrap();
+        self.seen_tables = seen_tables.into_inner().unwrap();
+        StateSet(solutions.into_inner().unwrap())
+    }
+
+    /// Like `solve`, but lazy: each call to `next()` advances the propagate-and-guess worklist
+    /// just far enough to produce one more solution, instead of running to completion up front.
+    /// This lets callers stop early, e.g. `solver.solutions().next()` for the first solution, or
+    /// `solver.solutions().take(k)` to bound how many are found.
+    pub fn solutions(&mut self) -> Solutions<'_, S> {
+        self.tables = self.begin_search();
+        Solutions {
+            solver: self,
+            start_time: Instant::now(),
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Compile `root` and the registered `constraints` to a CNF formula, as a scalable
+    /// alternative to `solve()`'s enumerate-and-guess search for large instances. Each variable
+    /// gets one boolean literal per remaining candidate value (one-hot: `Cnf::exactly_one`), and
+    /// each constraint is compiled to forbidden-tuple clauses by brute-force enumeration of its
+    /// parameters' candidate combinations, using the same `is_satisfied` check `simplify_table`
+    /// uses. That enumeration is exponential in the constraint's arity, so `to_cnf` only scales as
+    /// well as `root`'s domains have already been pruned; a `Permutation` or `GlobalCardinality`
+    /// over many large-domain variables will blow up here. See `solve_with_sat` to go straight to
+    /// a solved
+    /// `S`.
+    pub fn to_cnf(&self) -> sat::Encoding {
+        let mut cnf = sat::Cnf::new();
+        let mut literals: Vec<Vec<i64>> = Vec::with_capacity(self.root.entries.len());
+
+        for domain in &self.root.entries {
+            let lits = (0..domain.len()).map(|_| cnf.fresh_var()).collect::<Vec<_>>();
+            cnf.exactly_one(&lits);
+            literals.push(lits);
+        }
+
+        for constraint in &self.constraints {
+            let var_indices = constraint
+                .params
+                .iter()
+                .map(|var| self.root.vars.iter().position(|v| v == var).unwrap())
+                .collect::<Vec<_>>();
+
+            for assignment in entry_index_combinations::<S>(&var_indices, &self.root.entries) {
+                let mut table = self.root.clone();
+                for (&var_index, &entry_index) in var_indices.iter().zip(&assignment) {
+                    restrict_to_entry(&mut table, var_index, entry_index);
+                }
+                if (constraint.is_satisfied)(&table) == YesNoMaybe::No {
+                    let clause = var_indices
+                        .iter()
+                        .zip(&assignment)
+                        .map(|(&var_index, &entry_index)| -literals[var_index][entry_index])
+                        .collect();
+                    cnf.add_clause(clause);
+                }
+            }
+        }
+
+        sat::Encoding { cnf, literals }
+    }
+
+    /// Solve via `to_cnf` and an external SAT solver, as an alternative to `solve()`'s own
+    /// enumerate-and-guess search. Unlike `solve()`, this only ever returns a single solution (a
+    /// SAT solver reports one satisfying model, not every one), so it's best suited to puzzles
+    /// known to have a unique answer.
+    pub fn solve_with_sat(&self) -> Option<S> {
+        let encoding = self.to_cnf();
+
+        let mut solver = varisat::Solver::new();
+        for clause in &encoding.cnf.clauses {
+            let lits = clause
+                .iter()
+                .map(|&lit| varisat::Lit::from_dimacs(lit))
+                .collect::<Vec<_>>();
+            solver.add_clause(&lits);
+        }
+        if !solver.solve().unwrap_or(false) {
+            return None;
+        }
+        let model = solver.model()?;
+        let true_vars: HashSet<i64> = model
+            .iter()
+            .filter(|lit| lit.is_positive())
+            .map(|lit| lit.to_dimacs())
+            .collect();
+
+        let mut table = self.root.clone();
+        for (var_index, lits) in encoding.literals.iter().enumerate() {
+            let chosen = lits
+                .iter()
+                .position(|lit| true_vars.contains(lit))
+                .expect("solve_with_sat: SAT model didn't select exactly one value for a variable");
+            restrict_to_entry(&mut table, var_index, chosen);
+        }
+        Some(table.to_state(&self.metadata))
+    }
+}
+
+/// Iterator returned by `Solvomatic::solutions()`.
+pub struct Solutions<'a, S: State> {
+    solver: &'a mut Solvomatic<S>,
+    start_time: Instant,
+    last_tick: Instant,
+}
+
+impl<'a, S: State> Iterator for Solutions<'a, S> {
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        let solver = &mut *self.solver;
 
-        while !self.tables.is_empty() {
-            if self.config.log_states {
+        while !solver.tables.is_empty() {
+            solver.stats.peak_worklist_size =
+                solver.stats.peak_worklist_size.max(solver.tables.len());
+
+            if solver.config.log_states {
                 eprintln!(
                     "{}",
                     StateSet(
-                        self.tables
+                        solver
+                            .tables
                             .iter()
-                            .map(|table| table.to_state(&self.metadata))
+                            .map(|table| table.to_state(&solver.metadata))
                             .collect::<Vec<_>>()
                     )
                 )
             }
-            if self.config.log_steps {
+            if solver.config.log_steps {
                 eprintln!(
                     "Tables = {:3}, size = {:5}, possibilities = {}",
-                    self.tables.len(),
-                    self.size(),
-                    self.possibilities(),
+                    solver.tables.len(),
+                    solver.size(),
+                    solver.possibilities(),
                 );
             }
 
-            let table = self.tables.pop().unwrap();
-            if let Some(table) = self.simplify_table(table) {
+            let table = solver.pop_table().unwrap();
+            solver.stats.tables_popped += 1;
+            let mut solution = None;
+            if let Some(table) = solver.simplify_table(table) {
                 if table.is_solved() {
-                    self.solutions.push(table.to_state(&self.metadata));
+                    solution = Some(table.to_state(&solver.metadata));
                 } else {
-                    self.tables.extend(table.guess());
+                    solver.stats.guesses += 1;
+                    for child in table.guess() {
+                        if !solver.is_no_good(&child) && !solver.already_seen(&child) {
+                            solver.tables.push_back(child);
+                        }
+                    }
                 }
             }
-            if self.config.log_elapsed {
-                let elapsed_time = start_time.elapsed().as_millis();
+            let now = Instant::now();
+            solver.stats.elapsed += now.duration_since(self.last_tick);
+            self.last_tick = now;
+            if solver.config.log_elapsed {
+                let elapsed_time = self.start_time.elapsed().as_millis();
                 eprintln!("  elapsed: {:5?}ms", elapsed_time);
             }
+            if solution.is_some() {
+                return solution;
+            }
         }
-        if self.config.log_steps {
-            eprintln!("Total time: {}ms", start_time.elapsed().as_millis());
+        if solver.config.log_steps {
+            eprintln!("Total time: {}ms", self.start_time.elapsed().as_millis());
         }
+        None
+    }
+}
 
-        StateSet(mem::take(&mut self.solutions))
+/************************
+ *     Combinations     *
+ ************************/
+
+/// Every `k`-element subset of `items`, in lexicographic order of their indices into `items`.
+/// Yields one subset at a time, tracking only the current index set (in the style of itertools'
+/// `combinations`), rather than materializing the full powerset up front.
+struct Combinations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    k: usize,
+    done: bool,
+}
+
+impl<'a, T> Combinations<'a, T> {
+    fn new(items: &'a [T], k: usize) -> Combinations<'a, T> {
+        Combinations {
+            items,
+            indices: (0..k).collect(),
+            k,
+            done: k > items.len(),
+        }
+    }
+}
+
+impl<'a, T: Clone> Iterator for Combinations<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        let subset = self.indices.iter().map(|&i| self.items[i].clone()).collect();
+
+        // Advance to the next combination by finding the rightmost index that isn't already
+        // pinned against the end of `items`, bumping it, then resetting everything to its right.
+        let n = self.items.len();
+        match (0..self.k).rev().find(|&i| self.indices[i] != i + n - self.k) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in (i + 1)..self.k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(subset)
     }
 }
 
@@ -537,7 +1240,7 @@ impl<S: State> Solvomatic<S> {
 // When running `main`, this is loaded from command line args.
 // See `Config` in `main.rs`.
 /// Configuration options. Set these using `Solvomatic.config()`.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Config {
     /// Log after each step that's taken
     pub log_steps: bool,
@@ -549,4 +1252,105 @@ pub struct Config {
     pub log_elapsed: bool,
     /// Log intermediate states (these can be very large!)
     pub log_states: bool,
+    /// Order to explore the worklist of partial tables in.
+    pub search_mode: SearchMode,
+    /// Number of worker threads `solve()` distributes the table worklist across. `1` (the
+    /// default) solves single-threaded with no locking overhead; `solutions()`'s lazy iteration
+    /// is always single-threaded regardless of this, since stopping early doesn't make sense to
+    /// parallelize.
+    pub threads: usize,
+    /// Keep a cache of every guessed table's fingerprint (see `Table::fingerprint`) and skip
+    /// re-exploring a branch that lands on one already seen. Two different guess orders can reach
+    /// byte-for-byte identical tables, so this trades memory (one `u64` per table ever guessed)
+    /// for pruning duplicate work. Off by default.
+    pub dedupe_tables: bool,
+    /// Run the arc-consistency propagation loop (repeatedly deleting any candidate value some
+    /// constraint's `eval_constraint_for_all` marks `No`, to a fixpoint) before every `guess()`.
+    /// On by default, since it's what lets structured puzzles like sudoku get solved by deduction
+    /// rather than brute search. Turning it off falls back to pure blind branching, which is
+    /// mainly useful for measuring propagation's effect on `Stats::guesses` on a given puzzle.
+    pub propagate: bool,
+    /// Which rayon-backed strategy `solve()` uses to distribute work when `threads > 1`.
+    pub parallel_strategy: ParallelStrategy,
+    /// Only used by `ParallelStrategy::ForkJoin`: guess-tree depth below which branches are
+    /// still fanned out as separate rayon tasks (`par_iter`). Beyond this depth, a branch is
+    /// explored by plain sequential recursion instead, since by then its subtree is usually small
+    /// enough that spawning a task per guess costs more than it saves.
+    pub fork_join_depth: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            log_steps: false,
+            log_constraints: false,
+            log_completed: false,
+            log_elapsed: false,
+            log_states: false,
+            search_mode: SearchMode::default(),
+            threads: 1,
+            dedupe_tables: false,
+            propagate: true,
+            parallel_strategy: ParallelStrategy::default(),
+            fork_join_depth: 8,
+        }
+    }
+}
+
+/// Which rayon-backed strategy `solve()` uses to distribute work across `Config::threads`
+/// workers. Both explore the same guess tree and find the same solutions; they differ only in
+/// how work gets divided among threads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParallelStrategy {
+    /// A single shared queue of tables, pulled from by every worker thread (see `solve_parallel`).
+    /// Balances load well on irregular trees, at the cost of a mutex on every pop/push.
+    #[default]
+    WorkQueue,
+    /// Recursively fork each branch's children with `rayon`'s `par_iter`, down to
+    /// `Config::fork_join_depth`, then keep recursing sequentially (see `solve_fork_join`). Less
+    /// locking than `WorkQueue` near the root, but can leave threads idle if one early branch
+    /// turns out much larger than its siblings.
+    ForkJoin,
+}
+
+/// Which order `solve()`'s worklist of partial tables is explored in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Explore a branch all the way down before backtracking. Uses less memory, since the
+    /// worklist only holds the unexplored siblings of the tables along the current branch.
+    #[default]
+    DepthFirst,
+    /// Explore shallower partial tables before deeper ones, across all branches. Useful when
+    /// many shallow solutions are expected, or to see the "simplest" partial states first in
+    /// `log_states` output.
+    BreadthFirst,
+}
+
+/************************
+ *     Stats            *
+ ************************/
+
+/// Bookkeeping accumulated by `solve()`/`solutions()`, for benchmarking and comparing
+/// constraint encodings. Get the current numbers with `Solvomatic::stats()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    /// Number of tables popped off the worklist.
+    pub tables_popped: u64,
+    /// Number of times a table was split into children by `guess()`.
+    pub guesses: u64,
+    /// Number of `simplify_table` fixpoint iterations run.
+    pub propagation_rounds: u64,
+    /// Number of (var, entry) pairs deleted by propagation.
+    pub entries_deleted: u64,
+    /// Number of variables propagation alone narrowed down to a single remaining candidate
+    /// (solved without ever being the subject of a `guess()`), as opposed to becoming empty
+    /// (an immediate unsat, counted in `conflicts` instead).
+    pub variables_solved_by_propagation: u64,
+    /// Number of tables found unsatisfiable (`simplify_table` returned `None`).
+    pub conflicts: u64,
+    /// The largest the worklist grew to at the start of a step. Only the single-threaded search
+    /// (`solutions()`, and `solve()` with `threads <= 1`) updates it.
+    pub peak_worklist_size: usize,
+    /// Total time spent inside `solve`/`solutions`.
+    pub elapsed: std::time::Duration,
 }
@@ -6,10 +6,14 @@ use solvomatic::{Solvomatic, State};
 use std::collections::HashMap;
 use std::fmt;
 use std::fs;
+use std::io::Read;
 use std::iter::Peekable;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+
+mod repl;
 
 const STAR: i32 = -27; // entry representing '*'
 
@@ -290,6 +294,10 @@ enum BadInput {
     BadRangeEntry(i32),
     DoesNotMatchLayout(String, String),
     NoMatches(String),
+    CompareRuleDatas(usize),
+    CompareGroupMismatch(usize, usize),
+    BadPattern(String, String),
+    BadWordList(String, String),
 }
 
 impl fmt::Display for BadInput {
@@ -302,6 +310,22 @@ impl fmt::Display for BadInput {
             BadInput::NoMatches(input) => {
                 write!(f, "Pattern does not match the layout:\n{}", input)
             }
+            BadInput::CompareRuleDatas(n) => write!(
+                f,
+                "A comparison rule (lt/le/eq/ne) needs exactly two cell-group templates, got {}",
+                n
+            ),
+            BadInput::CompareGroupMismatch(left, right) => write!(
+                f,
+                "A comparison rule's two templates produced different numbers of cell groups ({} vs {})",
+                left, right
+            ),
+            BadInput::BadPattern(pattern, reason) => {
+                write!(f, "Bad pattern '{}': {}", pattern, reason)
+            }
+            BadInput::BadWordList(source, reason) => {
+                write!(f, "Failed to load word list '{}': {}", source, reason)
+            }
         }
     }
 }
@@ -325,6 +349,123 @@ enum PuzzleRule {
     Superset(Vec<i32>),
     Subset(Vec<i32>),
     InOrder(bool),
+    /// All cells in the group hold distinct values.
+    Distinct,
+    /// A binary comparison applied positionally between the two cell-groups of a rule set's two
+    /// templates (see `PuzzleDefinition::make_solver`'s comparison-rule branch).
+    Compare(CompareOp),
+    /// An arbitrary numeric predicate over the whole cell group, parsed from a small embedded
+    /// expression language (see `RuleExpr`).
+    Expr(RuleExpr),
+    /// A tiny regex over the entry alphabet that the cell group, taken in order, must match (see
+    /// `compile_pattern`).
+    Pattern(String),
+    /// Every cell in the group holds a pairwise-distinct value (same meaning as `Distinct`, but
+    /// checked with a scan in `make_solver` instead of the `AllDifferent` constraint).
+    AllDifferent,
+    /// Every cell in the group holds the same value.
+    AllSame,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl CompareOp {
+    fn holds(self, a: i32, b: i32) -> bool {
+        // Convert letters to numbers
+        let (a, b) = (a.abs(), b.abs());
+        match self {
+            CompareOp::Lt => a < b,
+            CompareOp::Le => a <= b,
+            CompareOp::Eq => a == b,
+            CompareOp::Ne => a != b,
+        }
+    }
+}
+
+/// The AST for an `expr` rule (`rule expr sum > 20 and max < 9`): a small embedded expression
+/// language over a rule's whole cell group, with integer/letter literals, aggregates over the
+/// group, arithmetic, comparisons, and booleans. Parsed by the `expr_p` family of parsers in
+/// `make_puzzle_parser`; to keep that grammar non-recursive like the rest of this file's, `count`
+/// takes a literal argument and `abs` a literal, aggregate or `count(...)` one, rather than an
+/// arbitrary nested expression, and there's no parenthesization.
+#[derive(Debug, Clone)]
+enum RuleExpr {
+    Int(i32),
+    Sum,
+    Prod,
+    Min,
+    Max,
+    Count(i32),
+    Abs(Box<RuleExpr>),
+    Add(Box<RuleExpr>, Box<RuleExpr>),
+    Sub(Box<RuleExpr>, Box<RuleExpr>),
+    Mul(Box<RuleExpr>, Box<RuleExpr>),
+    Div(Box<RuleExpr>, Box<RuleExpr>),
+    Mod(Box<RuleExpr>, Box<RuleExpr>),
+    Eq(Box<RuleExpr>, Box<RuleExpr>),
+    Ne(Box<RuleExpr>, Box<RuleExpr>),
+    Lt(Box<RuleExpr>, Box<RuleExpr>),
+    Le(Box<RuleExpr>, Box<RuleExpr>),
+    Gt(Box<RuleExpr>, Box<RuleExpr>),
+    Ge(Box<RuleExpr>, Box<RuleExpr>),
+    And(Box<RuleExpr>, Box<RuleExpr>),
+    Or(Box<RuleExpr>, Box<RuleExpr>),
+    Not(Box<RuleExpr>),
+}
+
+impl RuleExpr {
+    /// Walk the AST bottom-up over `elems`, the matched cell group. Aggregates fold over the
+    /// whole of `elems` (converting letters to numbers via `abs()`, just as `InOrder` does);
+    /// comparisons and booleans yield 0/1. Returns `None` for a `/` or `%` by zero, rather than
+    /// panicking -- a candidate assignment that makes the divisor zero just fails the rule.
+    fn eval(&self, elems: &[i32]) -> Option<i64> {
+        use RuleExpr::*;
+        let as_numbers = || elems.iter().map(|n| n.abs() as i64);
+        Some(match self {
+            // Convert letters to numbers, same as everywhere else here -- `entry_p` parses a
+            // bare literal as either a letter or a numeral, and a letter literal should compare
+            // the same as the letter values `elems` holds (also converted via `abs()`).
+            Int(n) => n.abs() as i64,
+            Sum => as_numbers().sum(),
+            Prod => as_numbers().product(),
+            Min => as_numbers().min().unwrap_or(0),
+            Max => as_numbers().max().unwrap_or(0),
+            Count(v) => as_numbers().filter(|n| *n == v.abs() as i64).count() as i64,
+            Abs(a) => a.eval(elems)?.abs(),
+            Add(a, b) => a.eval(elems)? + b.eval(elems)?,
+            Sub(a, b) => a.eval(elems)? - b.eval(elems)?,
+            Mul(a, b) => a.eval(elems)? * b.eval(elems)?,
+            Div(a, b) => {
+                let (a, b) = (a.eval(elems)?, b.eval(elems)?);
+                if b == 0 {
+                    return None;
+                }
+                a / b
+            }
+            Mod(a, b) => {
+                let (a, b) = (a.eval(elems)?, b.eval(elems)?);
+                if b == 0 {
+                    return None;
+                }
+                a % b
+            }
+            Eq(a, b) => (a.eval(elems)? == b.eval(elems)?) as i64,
+            Ne(a, b) => (a.eval(elems)? != b.eval(elems)?) as i64,
+            Lt(a, b) => (a.eval(elems)? < b.eval(elems)?) as i64,
+            Le(a, b) => (a.eval(elems)? <= b.eval(elems)?) as i64,
+            Gt(a, b) => (a.eval(elems)? > b.eval(elems)?) as i64,
+            Ge(a, b) => (a.eval(elems)? >= b.eval(elems)?) as i64,
+            And(a, b) => ((a.eval(elems)? != 0) && (b.eval(elems)? != 0)) as i64,
+            Or(a, b) => ((a.eval(elems)? != 0) || (b.eval(elems)? != 0)) as i64,
+            Not(a) => (a.eval(elems)? == 0) as i64,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -341,8 +482,14 @@ struct PuzzleDefinition {
     initial: Option<String>,
 }
 
+/// Built-in dictionary aliases a `word` rule can name instead of a literal path.
+const BUILT_IN_WORD_LISTS: &[(&str, &str)] = &[("dict", "/usr/share/dict/words")];
+
 struct WordListLoader {
-    cache: HashMap<PathBuf, String>,
+    /// Cached by `(path or URL, word_len)`, so a given dictionary is only read/fetched and
+    /// filtered once per word length, however many `word` rules of that length name it; a rule
+    /// of a new length reads it again.
+    cache: HashMap<(String, usize), solvomatic::constraints::Seq<i32>>,
 }
 
 impl WordListLoader {
@@ -352,28 +499,408 @@ impl WordListLoader {
         }
     }
 
-    fn load(&mut self, path: &str, word_len: usize) -> solvomatic::constraints::Seq<i32> {
-        let word_list = self
-            .cache
-            .entry(PathBuf::from(path))
-            .or_insert_with(|| fs::read_to_string(path).expect("Failed to load word list"));
+    /// Resolve a `word` rule's argument against the built-in aliases (see
+    /// `BUILT_IN_WORD_LISTS`), falling back to treating it as a literal filesystem path or URL.
+    fn resolve_path(name_or_path: &str) -> &str {
+        BUILT_IN_WORD_LISTS
+            .iter()
+            .find(|(name, _)| *name == name_or_path)
+            .map(|(_, path)| *path)
+            .unwrap_or(name_or_path)
+    }
+
+    fn load(
+        &mut self,
+        name_or_path: &str,
+        word_len: usize,
+    ) -> Result<solvomatic::constraints::Seq<i32>, BadInput> {
+        let source = Self::resolve_path(name_or_path).to_owned();
+        let key = (source.clone(), word_len);
+
+        if let Some(seq) = self.cache.get(&key) {
+            return Ok(seq.clone());
+        }
+
+        let word_list = if source.starts_with("http://") || source.starts_with("https://") {
+            fetch_word_list_url(&source)?
+        } else {
+            fs::read_to_string(&source)
+                .map_err(|err| BadInput::BadWordList(source.clone(), err.to_string()))?
+        };
 
+        // Reuse `read_letter`, instead of re-deriving the letter->number offset, so a word rule's
+        // encoding always matches how `Entry::parse`/`Display` read and print the same letters.
         let words = word_list
             .lines()
             .map(|s| s.trim())
             .map(|s| s.to_lowercase())
             .filter(|s| s.chars().count() == word_len)
-            .map(|s| s.chars().map(|ch| 96 - (ch as i32)).collect::<Vec<_>>());
+            // Skip entries with punctuation (e.g. "don't"), which `read_letter` can't encode.
+            .filter(|s| s.chars().all(|ch| ch.is_ascii_alphabetic()))
+            .map(|s| {
+                s.chars()
+                    .map(|ch| read_letter(ch).expect("word list entry must be all-alphabetic"))
+                    .collect::<Vec<_>>()
+            });
+
+        let seq = solvomatic::constraints::Seq::new(word_len, words);
+        self.cache.insert(key, seq.clone());
+        Ok(seq)
+    }
+}
+
+/// Where fetched word lists are cached on disk, so a `word http(s)://...` rule doesn't re-fetch
+/// its dictionary on every run. Honors `XDG_CACHE_HOME`, then falls back to `~/.cache`, then to
+/// the system temp directory if neither is set.
+fn word_list_cache_dir() -> PathBuf {
+    let base = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|_| std::env::temp_dir());
+    base.join("solvomatic").join("word_lists")
+}
+
+/// The deterministic cache file a `url` is stored under, keyed by a hash of the URL itself.
+fn word_list_cache_file(url: &str) -> PathBuf {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    word_list_cache_dir().join(format!("{:016x}.txt", hasher.finish()))
+}
+
+/// How long to wait on a `word http(s)://...` fetch before giving up, so a slow or unresponsive
+/// server can't hang the solver indefinitely.
+const WORD_LIST_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The largest response `fetch_word_list_url` will accept, so a malicious or misbehaving server
+/// can't flood memory/disk with an unbounded body. No built-in or realistic word list is anywhere
+/// close to this size.
+const WORD_LIST_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Fetch a word list's contents from `url`, reusing the on-disk cache file from a previous run if
+/// it's there, and writing to it on a successful fetch.
+fn fetch_word_list_url(url: &str) -> Result<String, BadInput> {
+    let cache_file = word_list_cache_file(url);
+    if let Ok(contents) = fs::read_to_string(&cache_file) {
+        return Ok(contents);
+    }
+
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(WORD_LIST_FETCH_TIMEOUT)
+        .timeout(WORD_LIST_FETCH_TIMEOUT)
+        .build();
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|err| BadInput::BadWordList(url.to_owned(), err.to_string()))?;
+
+    let mut body = String::new();
+    // Read one byte past the limit, so we can tell "exactly at the limit" apart from "truncated".
+    response
+        .into_reader()
+        .take(WORD_LIST_MAX_BYTES + 1)
+        .read_to_string(&mut body)
+        .map_err(|err| BadInput::BadWordList(url.to_owned(), err.to_string()))?;
+    if body.len() as u64 > WORD_LIST_MAX_BYTES {
+        return Err(BadInput::BadWordList(
+            url.to_owned(),
+            format!("response exceeded the {WORD_LIST_MAX_BYTES}-byte limit"),
+        ));
+    }
+
+    if let Some(parent) = cache_file.parent() {
+        // Caching is an optimization, not a correctness requirement -- if the cache directory
+        // can't be created or written, just skip caching rather than failing the fetch.
+        if fs::create_dir_all(parent).is_ok() {
+            let _ = fs::write(&cache_file, &body);
+        }
+    }
+
+    Ok(body)
+}
+
+/// One step of a `pattern` rule's regex: a matcher for a single entry, plus how many times it may
+/// repeat. Quantifiers apply only to the matcher right before them -- there's no grouping, so
+/// `a*b` means "zero or more `a`s, then a `b`", not "zero or more `ab`s".
+#[derive(Debug, Clone, Copy)]
+enum Quantifier {
+    One,
+    ZeroOrOne,
+    ZeroOrMore,
+    OneOrMore,
+}
+
+/// What a single position in a `pattern` rule's regex can match against one entry.
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// `.`: any entry.
+    Any,
+    /// A literal letter or digit.
+    Symbol(i32),
+    /// `[...]`: one of a fixed list of letters/digits (ranges like `a-f` or `0-9` are expanded
+    /// into this list when the pattern is parsed).
+    Class(Vec<i32>),
+}
+
+impl Matcher {
+    fn matches(&self, elem: i32) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Symbol(symbol) => *symbol == elem,
+            Matcher::Class(members) => members.contains(&elem),
+        }
+    }
+}
 
-        solvomatic::constraints::Seq::new(word_len, words)
+/// A compiled `pattern` rule: an NFA over the entry alphabet, built once by `compile_pattern` and
+/// then run left-to-right against a candidate cell-group assignment (see `PuzzleRule::Pattern`).
+/// `transitions[state]` lists the ways to leave `state`: an epsilon move (`None`) or a move that
+/// consumes one entry matching a `Matcher` (`Some`).
+#[derive(Debug, Clone)]
+struct Nfa {
+    transitions: Vec<Vec<(Option<Matcher>, usize)>>,
+    start: usize,
+    accept: usize,
+}
+
+impl Nfa {
+    /// Extend `states` (indexed by state number) with everything reachable from it via epsilon
+    /// moves alone.
+    fn epsilon_closure(&self, states: &mut [bool]) {
+        let mut frontier: Vec<usize> = (0..states.len()).filter(|&state| states[state]).collect();
+        while let Some(state) = frontier.pop() {
+            for (matcher, target) in &self.transitions[state] {
+                if matcher.is_none() && !states[*target] {
+                    states[*target] = true;
+                    frontier.push(*target);
+                }
+            }
+        }
+    }
+
+    /// Run the NFA left-to-right over `elems`, accepting iff an accepting state is reachable once
+    /// every element has been consumed. Tracks the live state set as a `Vec<bool>` indexed by
+    /// state number, since this is called once per candidate assignment the solver tries.
+    fn accepts(&self, elems: &[i32]) -> bool {
+        let mut current = vec![false; self.transitions.len()];
+        current[self.start] = true;
+        self.epsilon_closure(&mut current);
+        for &elem in elems {
+            let mut next = vec![false; self.transitions.len()];
+            for (state, &active) in current.iter().enumerate() {
+                if !active {
+                    continue;
+                }
+                for (matcher, target) in &self.transitions[state] {
+                    if matcher.as_ref().is_some_and(|matcher| matcher.matches(elem)) {
+                        next[*target] = true;
+                    }
+                }
+            }
+            self.epsilon_closure(&mut next);
+            current = next;
+        }
+        current[self.accept]
+    }
+}
+
+fn new_nfa_state(transitions: &mut Vec<Vec<(Option<Matcher>, usize)>>) -> usize {
+    transitions.push(Vec::new());
+    transitions.len() - 1
+}
+
+/// Parse a `pattern`/`regex` rule's argument into a matcher-and-quantifier for each position.
+fn parse_pattern_terms(pattern: &str) -> Result<Vec<(Matcher, Quantifier)>, String> {
+    let mut chars = pattern.chars().peekable();
+    let mut terms = Vec::new();
+    while let Some(ch) = chars.next() {
+        let matcher = match ch {
+            '.' => Matcher::Any,
+            '[' => Matcher::Class(parse_pattern_class(&mut chars)?),
+            _ => Matcher::Symbol(encode_pattern_char(ch)?),
+        };
+        let quantifier = match chars.peek() {
+            Some('*') => {
+                chars.next();
+                Quantifier::ZeroOrMore
+            }
+            Some('+') => {
+                chars.next();
+                Quantifier::OneOrMore
+            }
+            Some('?') => {
+                chars.next();
+                Quantifier::ZeroOrOne
+            }
+            _ => Quantifier::One,
+        };
+        terms.push((matcher, quantifier));
+    }
+    if terms.is_empty() {
+        return Err("pattern must not be empty".to_string());
+    }
+    Ok(terms)
+}
+
+/// Parse a `[...]` character class, starting just after the `[`. Supports individual letters and
+/// digits, plus same-kind ranges like `a-f` or `0-9`.
+fn parse_pattern_class(
+    chars: &mut Peekable<impl Iterator<Item = char> + Clone>,
+) -> Result<Vec<i32>, String> {
+    let mut members = Vec::new();
+    loop {
+        let lo_ch = chars
+            .next()
+            .ok_or_else(|| "unterminated character class (missing ']')".to_string())?;
+        if lo_ch == ']' {
+            break;
+        }
+        if chars.peek() == Some(&'-') {
+            let mut lookahead = chars.clone();
+            lookahead.next(); // the '-'
+            if let Some(hi_ch) = lookahead.next().filter(|&ch| ch != ']') {
+                chars.next(); // the '-'
+                chars.next(); // hi_ch
+                members.extend(expand_pattern_range(lo_ch, hi_ch)?);
+                continue;
+            }
+        }
+        members.push(encode_pattern_char(lo_ch)?);
+    }
+    Ok(members)
+}
+
+/// Expand a character-class range like `a-f` or `0-9` into the entries it denotes.
+fn expand_pattern_range(lo_ch: char, hi_ch: char) -> Result<Vec<i32>, String> {
+    if lo_ch.is_ascii_alphabetic() != hi_ch.is_ascii_alphabetic() || lo_ch > hi_ch {
+        return Err(format!("bad character class range '{}-{}'", lo_ch, hi_ch));
+    }
+    (lo_ch as u8..=hi_ch as u8)
+        .map(|code| encode_pattern_char(code as char))
+        .collect()
+}
+
+/// Encode one pattern character into the entry alphabet: a letter the same way `read_letter`
+/// does, or a single digit as its value. Unlike `Entry::parse`, a run of digits in a pattern is
+/// matched digit-by-digit rather than as one multi-digit numeral -- `[0-9][0-9]` matches two
+/// adjacent single-digit cells, not one two-digit one.
+fn encode_pattern_char(ch: char) -> Result<i32, String> {
+    read_letter(ch)
+        .or_else(|| ch.to_digit(10).map(|d| d as i32))
+        .ok_or_else(|| format!("'{}' is not a letter or digit", ch))
+}
+
+/// Compile a `pattern`/`regex` rule's argument into an NFA, via the classic per-atom Thompson
+/// construction (each matcher-and-quantifier becomes a small fragment, concatenated in sequence;
+/// there's no grouping or alternation to construct around).
+fn compile_pattern(pattern: &str) -> Result<Nfa, String> {
+    let terms = parse_pattern_terms(pattern)?;
+    let mut transitions: Vec<Vec<(Option<Matcher>, usize)>> = Vec::new();
+    let start = new_nfa_state(&mut transitions);
+    let mut prev_accept = start;
+    for (matcher, quantifier) in terms {
+        let frag_start = new_nfa_state(&mut transitions);
+        let frag_accept = new_nfa_state(&mut transitions);
+        transitions[prev_accept].push((None, frag_start));
+        match quantifier {
+            Quantifier::One => transitions[frag_start].push((Some(matcher), frag_accept)),
+            Quantifier::ZeroOrOne => {
+                transitions[frag_start].push((Some(matcher), frag_accept));
+                transitions[frag_start].push((None, frag_accept));
+            }
+            Quantifier::ZeroOrMore => {
+                let loop_in = new_nfa_state(&mut transitions);
+                transitions[frag_start].push((None, loop_in));
+                transitions[frag_start].push((None, frag_accept));
+                transitions[loop_in].push((Some(matcher), frag_accept));
+                transitions[frag_accept].push((None, loop_in));
+            }
+            Quantifier::OneOrMore => {
+                let loop_in = new_nfa_state(&mut transitions);
+                transitions[frag_start].push((None, loop_in));
+                transitions[loop_in].push((Some(matcher), frag_accept));
+                transitions[frag_accept].push((None, loop_in));
+            }
+        }
+        prev_accept = frag_accept;
+    }
+    Ok(Nfa {
+        transitions,
+        start,
+        accept: prev_accept,
+    })
+}
+
+struct PatternCache {
+    /// Cached by the pattern string itself, so a `pattern`/`regex` rule only compiles its NFA once
+    /// no matter how many cell groups it's applied to (mirrors `WordListLoader::cache`).
+    cache: HashMap<String, Nfa>,
+}
+
+impl PatternCache {
+    fn new() -> PatternCache {
+        PatternCache {
+            cache: HashMap::new(),
+        }
+    }
+
+    fn load(&mut self, pattern: &str) -> Result<Nfa, String> {
+        if let Some(nfa) = self.cache.get(pattern) {
+            return Ok(nfa.clone());
+        }
+        let nfa = compile_pattern(pattern)?;
+        self.cache.insert(pattern.to_owned(), nfa.clone());
+        Ok(nfa)
+    }
+}
+
+/// Parse one rule set's data template against `layout` and group its marked cells into var lists
+/// the same way `sum`/`prod`/etc. do: cells sharing a letter key form one group, and all the
+/// plain-numbered cells (sorted by number) form another. Shared by the ordinary per-var_list rule
+/// loop and the comparison-rule branch in `PuzzleDefinition::make_solver`, which needs the groups
+/// from two templates kept separate instead of flattened together.
+fn group_vars_by_key(layout: &Layout, data: &str) -> Result<Vec<Vec<usize>>, BadInput> {
+    let mut var_lists = Vec::new();
+    for entries in layout.parse_sub_input(data)? {
+        let mut key_to_var_list = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            if let Entry(Some(entry)) = entry {
+                let key = if *entry >= 0 {
+                    // entry was a number
+                    None
+                } else {
+                    // entry was a letter, or '*'
+                    Some(entry)
+                };
+                key_to_var_list
+                    .entry(key)
+                    .or_insert_with(Vec::new)
+                    .push((i, entry))
+            }
+        }
+        // Sort groups by key (the `None` numeric group first, then letter/`'*'`-keyed groups in
+        // ascending order) instead of leaving them in `HashMap`'s unspecified iteration order:
+        // the `Compare` branch of `make_solver` zips two templates' groups together positionally,
+        // so group `i` here has to mean the same key as group `i` from the other template's call.
+        let mut keyed_var_lists = key_to_var_list.into_iter().collect::<Vec<_>>();
+        keyed_var_lists.sort_by_key(|(key, _)| *key);
+        for (key, mut var_list) in keyed_var_lists {
+            if key.is_none() {
+                var_list.sort_by_key(|(_, entry)| *entry);
+            }
+            let var_list = var_list.into_iter().map(|(var, _)| var).collect::<Vec<_>>();
+            var_lists.push(var_list)
+        }
     }
+    Ok(var_lists)
 }
 
 impl PuzzleDefinition {
     fn make_solver(self, config: Config) -> Result<Solvomatic<Data>, BadInput> {
-        use solvomatic::constraints::{Permutation, Pred, Prod, Subset, Sum, Superset};
+        use solvomatic::constraints::{AllDifferent, Permutation, Pred, Prod, Subset, Sum, Superset};
 
         let mut word_list_loader = WordListLoader::new();
+        let mut pattern_cache = PatternCache::new();
 
         let original_layout = Layout::new(&self.layout);
         let layout = Arc::new(original_layout.clone());
@@ -386,6 +913,15 @@ impl PuzzleDefinition {
             solver.config().log_elapsed = config.log_elapsed;
             solver.config().log_states = config.log_states;
         }
+        solver.config().threads = config.threads;
+        solver.config().dedupe_tables = config.dedupe_tables;
+        solver.config().propagate = !config.no_propagate;
+        solver.config().parallel_strategy = match config.parallel_strategy.as_str() {
+            "work-queue" => solvomatic::ParallelStrategy::WorkQueue,
+            "fork-join" => solvomatic::ParallelStrategy::ForkJoin,
+            other => panic!("Unknown --parallel-strategy '{}' (expected 'work-queue' or 'fork-join')", other),
+        };
+        solver.config().fork_join_depth = config.fork_join_depth;
 
         for range in &self.ranges {
             let data = Data::new(&range.data, layout.clone())?;
@@ -399,33 +935,47 @@ impl PuzzleDefinition {
         }
 
         for rule_set in &self.rule_sets {
-            let mut var_lists = Vec::new();
-            for data in &rule_set.datas {
-                for entries in layout.parse_sub_input(data)? {
-                    let mut key_to_var_list = HashMap::new();
-                    for (i, entry) in entries.iter().enumerate() {
-                        if let Entry(Some(entry)) = entry {
-                            let key = if *entry >= 0 {
-                                // entry was a number
-                                None
-                            } else {
-                                // entry was a letter, or '*'
-                                Some(entry)
-                            };
-                            key_to_var_list
-                                .entry(key)
-                                .or_insert_with(Vec::new)
-                                .push((i, entry))
-                        }
-                    }
-                    for (key, mut var_list) in key_to_var_list {
-                        if key.is_none() {
-                            var_list.sort_by_key(|(_, entry)| *entry);
+            let is_compare = rule_set
+                .rules
+                .iter()
+                .any(|rule| matches!(rule, PuzzleRule::Compare(_)));
+
+            if is_compare {
+                if rule_set.datas.len() != 2 {
+                    return Err(BadInput::CompareRuleDatas(rule_set.datas.len()));
+                }
+                let left_lists = group_vars_by_key(&layout, &rule_set.datas[0])?;
+                let right_lists = group_vars_by_key(&layout, &rule_set.datas[1])?;
+                if left_lists.len() != right_lists.len() {
+                    return Err(BadInput::CompareGroupMismatch(
+                        left_lists.len(),
+                        right_lists.len(),
+                    ));
+                }
+                for (left, right) in left_lists.iter().zip(&right_lists) {
+                    let mid = left.len();
+                    let vars = left.iter().chain(right).copied();
+                    for rule in &rule_set.rules {
+                        if let PuzzleRule::Compare(op) = rule {
+                            let op = *op;
+                            solver.constraint(
+                                vars.clone(),
+                                Pred::with_len(left.len() + right.len(), move |elems: &[i32]| {
+                                    elems[..mid]
+                                        .iter()
+                                        .zip(&elems[mid..])
+                                        .all(|(a, b)| op.holds(*a, *b))
+                                }),
+                            )
                         }
-                        let var_list = var_list.into_iter().map(|(var, _)| var).collect::<Vec<_>>();
-                        var_lists.push(var_list)
                     }
                 }
+                continue;
+            }
+
+            let mut var_lists = Vec::new();
+            for data in &rule_set.datas {
+                var_lists.extend(group_vars_by_key(&layout, data)?);
             }
             for var_list in var_lists {
                 for rule in &rule_set.rules {
@@ -434,7 +984,7 @@ impl PuzzleDefinition {
                         PuzzleRule::Sum(sum) => solver.constraint(vars, Sum::new(*sum)),
                         PuzzleRule::Prod(prod) => solver.constraint(vars, Prod::new(*prod)),
                         PuzzleRule::Word(path) => {
-                            let words = word_list_loader.load(path, var_list.len());
+                            let words = word_list_loader.load(path, var_list.len())?;
                             solver.constraint(vars, words);
                         }
                         PuzzleRule::Permutation(permutation) => {
@@ -463,6 +1013,50 @@ impl PuzzleDefinition {
                                 },
                             )
                         }
+                        PuzzleRule::Distinct => solver.constraint(vars, AllDifferent::new()),
+                        PuzzleRule::Expr(expr) => {
+                            let expr = expr.clone();
+                            solver.constraint(
+                                vars,
+                                Pred::with_len(var_list.len(), move |elems: &[i32]| {
+                                    expr.eval(elems).is_some_and(|n| n != 0)
+                                }),
+                            )
+                        }
+                        PuzzleRule::Pattern(pattern) => {
+                            let nfa = pattern_cache
+                                .load(pattern)
+                                .map_err(|reason| BadInput::BadPattern(pattern.clone(), reason))?;
+                            solver.constraint(
+                                vars,
+                                Pred::with_len(var_list.len(), move |elems: &[i32]| {
+                                    nfa.accepts(elems)
+                                }),
+                            )
+                        }
+                        PuzzleRule::AllDifferent => {
+                            let len = var_list.len();
+                            solver.constraint(
+                                vars,
+                                Pred::with_len(len, |elems: &[i32]| {
+                                    (0..elems.len()).all(|i| !elems[i + 1..].contains(&elems[i]))
+                                }),
+                            )
+                        }
+                        PuzzleRule::AllSame => {
+                            let len = var_list.len();
+                            solver.constraint(
+                                vars,
+                                Pred::with_len(len, |elems: &[i32]| {
+                                    elems.iter().all(|&e| e == elems[0])
+                                }),
+                            )
+                        }
+                        PuzzleRule::Compare(_) => {
+                            // Handled above, in the rule set's dedicated comparison branch:
+                            // comparison rules pair up two templates' cell-groups positionally
+                            // instead of folding every rule over one flat var_list.
+                        }
                     }
                 }
             }
@@ -556,8 +1150,9 @@ fn make_puzzle_parser() -> Result<impl CompiledParser<PuzzleDefinition>, Grammar
     // rule name arg...
     //   DATA
     //   ...
+    // A filesystem path, or an `http(s)://` URL (see `WordListLoader::load`).
     let path = g
-        .regex("path", "([_/a-zA-Z0-9-]|\\.[_a-zA-Z])+")?
+        .regex("path", "[A-Za-z0-9_./:?=&%~+-]+")?
         .span(|span| span.substr.to_owned());
     let sum_p = entry_p
         .clone()
@@ -579,23 +1174,151 @@ fn make_puzzle_parser() -> Result<impl CompiledParser<PuzzleDefinition>, Grammar
         (g.string("superset")?, entry_set_p.clone()),
     )
     .map(|(_, entries)| PuzzleRule::Superset(entries));
+    // `word PATH`: PATH is a literal dictionary file path, an `http(s)://` URL, or a built-in
+    // alias (see `BUILT_IN_WORD_LISTS`, e.g. "dict"), resolved by `WordListLoader::resolve_path`.
+    // An `http(s)://` PATH is fetched with a timeout and a response-size cap (see
+    // `fetch_word_list_url`), but isn't restricted to any host allowlist -- only run puzzle files
+    // whose `word` rules you trust, the same as you'd trust any other URL a program fetches for you.
     let word_p = path.preceded(g.string("word")?).map(PuzzleRule::Word);
     let in_order_p = g.string("in_order")?.constant(PuzzleRule::InOrder(true));
     let in_reverse_order_p = g
         .string("in_reverse_order")?
         .constant(PuzzleRule::InOrder(false));
+    let distinct_p = g.string("distinct")?.constant(PuzzleRule::Distinct);
+    let diff_p = g.string("diff")?.constant(PuzzleRule::Distinct);
+    let all_different_p = g
+        .string("all_different")?
+        .constant(PuzzleRule::AllDifferent);
+    let all_same_p = g.string("all_same")?.constant(PuzzleRule::AllSame);
+    // `pattern PATTERN` / `regex PATTERN`: PATTERN is a tiny regex over the entry alphabet (see
+    // `compile_pattern`) -- literal letters/digits, '.', character classes like '[a-f0-9]', and
+    // the '*'/'+'/'?' quantifiers, concatenated with no grouping or alternation. Compiled lazily
+    // in `make_solver`, like `word`'s path is only resolved to a word list there.
+    let pattern_str_p = g
+        .regex("pattern", "[-a-zA-Z0-9.\\[\\]*+?]+")?
+        .span(|span| span.substr.to_owned());
+    let pattern_p = pattern_str_p
+        .clone()
+        .preceded(g.string("pattern")?)
+        .map(PuzzleRule::Pattern);
+    let regex_p = pattern_str_p
+        .preceded(g.string("regex")?)
+        .map(PuzzleRule::Pattern);
+    let lt_p = g.string("lt")?.constant(PuzzleRule::Compare(CompareOp::Lt));
+    let le_p = g.string("le")?.constant(PuzzleRule::Compare(CompareOp::Le));
+    let eq_p = g.string("eq")?.constant(PuzzleRule::Compare(CompareOp::Eq));
+    let ne_p = g.string("ne")?.constant(PuzzleRule::Compare(CompareOp::Ne));
+
+    // `expr EXPR`: a small, non-recursive expression language (see `RuleExpr`), built bottom-up
+    // by precedence level the same way `entry_set_p` folds a repeated pattern, rather than via a
+    // recursive grammar (this crate's combinators elsewhere never need to refer to themselves, so
+    // we don't introduce the first one here). `count` therefore takes a literal argument and `abs`
+    // a literal, aggregate or `count(...)` one, instead of an arbitrary sub-expression, and
+    // there's no parenthesization.
+    let agg_p = choice(
+        "aggregate ('sum', 'prod', 'min', 'max')",
+        (
+            g.string("sum")?.constant(RuleExpr::Sum),
+            g.string("prod")?.constant(RuleExpr::Prod),
+            g.string("min")?.constant(RuleExpr::Min),
+            g.string("max")?.constant(RuleExpr::Max),
+        ),
+    );
+    let count_p = tuple(
+        "count(v)",
+        (g.string("count")?, g.string("(")?, entry_p.clone(), g.string(")")?),
+    )
+    .map(|(_, _, v, _)| RuleExpr::Count(v));
+    let simple_p = choice("expression atom", (count_p, agg_p, entry_p.clone().map(RuleExpr::Int)));
+    let abs_p = tuple(
+        "abs(...)",
+        (g.string("abs")?, g.string("(")?, simple_p.clone(), g.string(")")?),
+    )
+    .map(|(_, _, e, _)| RuleExpr::Abs(Box::new(e)));
+    let atom_p = choice("expression atom", (abs_p, simple_p));
+
+    let mul_op_p = choice(
+        "'*', '/', or '%'",
+        (
+            g.string("*")?.constant(RuleExpr::Mul as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("/")?.constant(RuleExpr::Div as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("%")?.constant(RuleExpr::Mod as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+        ),
+    );
+    let mul_rhs_p = tuple("multiplicative op and operand", (mul_op_p, atom_p.clone()))
+        .many1()
+        .opt();
+    let mul_p = tuple("multiplicative expression", (atom_p, mul_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, (op, rhs)| op(Box::new(acc), Box::new(rhs)))
+    });
+
+    let add_op_p = choice(
+        "'+' or '-'",
+        (
+            g.string("+")?.constant(RuleExpr::Add as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("-")?.constant(RuleExpr::Sub as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+        ),
+    );
+    let add_rhs_p = tuple("additive op and operand", (add_op_p, mul_p.clone())).many1().opt();
+    let add_p = tuple("additive expression", (mul_p, add_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, (op, rhs)| op(Box::new(acc), Box::new(rhs)))
+    });
+
+    let cmp_op_p = choice(
+        "'=', '!=', '<', '<=', '>', or '>='",
+        (
+            g.string("!=")?.constant(RuleExpr::Ne as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("<=")?.constant(RuleExpr::Le as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string(">=")?.constant(RuleExpr::Ge as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("=")?.constant(RuleExpr::Eq as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("<")?.constant(RuleExpr::Lt as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string(">")?.constant(RuleExpr::Gt as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+        ),
+    );
+    let cmp_rhs_p = tuple("comparison op and operand", (cmp_op_p, add_p.clone())).opt();
+    let cmp_p = tuple("comparison expression", (add_p, cmp_rhs_p)).map(|(first, opt_rhs)| {
+        match opt_rhs {
+            Some((op, rhs)) => op(Box::new(first), Box::new(rhs)),
+            None => first,
+        }
+    });
+
+    let not_p = choice(
+        "expression",
+        (
+            cmp_p.clone().preceded(g.string("not")?).map(|e| RuleExpr::Not(Box::new(e))),
+            cmp_p,
+        ),
+    );
+    let and_rhs_p = not_p.clone().preceded(g.string("and")?).many1().opt();
+    let and_p = tuple("'and' expression", (not_p, and_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, rhs| RuleExpr::And(Box::new(acc), Box::new(rhs)))
+    });
+    let or_rhs_p = and_p.clone().preceded(g.string("or")?).many1().opt();
+    let or_p = tuple("'or' expression", (and_p, or_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, rhs| RuleExpr::Or(Box::new(acc), Box::new(rhs)))
+    });
+    let expr_p = or_p.preceded(g.string("expr")?).map(PuzzleRule::Expr);
 
+    let pattern_or_regex_p = choice("rule name", (pattern_p, regex_p));
+    let ne_and_beyond_p = choice("rule name", (ne_p, choice("rule name", (expr_p, pattern_or_regex_p))));
     let rule_p = choice(
-        "rule name ('sum', 'prod', 'word', 'permutation', 'subset', 'supserset', 'in_order')",
+        "rule name ('sum', 'prod', 'word', 'permutation', 'subset', 'superset', 'in_order', \
+         'in_reverse_order', 'distinct', 'diff', 'all_different', 'all_same', 'lt', 'le', 'eq', \
+         'ne', 'expr', 'pattern', 'regex')",
         (
-            sum_p,
-            prod_p,
-            word_p,
-            permutation_p,
-            subset_p,
-            superset_p,
-            in_order_p,
-            in_reverse_order_p,
+            choice("rule name", (sum_p, prod_p, word_p, permutation_p)),
+            choice("rule name", (subset_p, superset_p, in_order_p, in_reverse_order_p)),
+            choice("rule name", (distinct_p, diff_p, all_different_p, all_same_p)),
+            choice("rule name", (lt_p, le_p, eq_p, ne_and_beyond_p)),
         ),
     );
     let rules_p = rule_p.preceded(g.string("rule")?).many1();
@@ -645,9 +1368,14 @@ fn make_puzzle_parser() -> Result<impl CompiledParser<PuzzleDefinition>, Grammar
 /// solv-o-matic
 #[derive(Debug, Clone, FromArgs)]
 struct Config {
-    /// the puzzle definition file to run
+    /// the puzzle definition file to run. Omit this (with `--repl`) to start an interactive
+    /// session instead.
     #[argh(positional)]
-    filename: String,
+    filename: Option<String>,
+
+    /// start an interactive REPL (see `repl` module) instead of running a puzzle file
+    #[argh(switch, long = "repl")]
+    repl: bool,
 
     /// don't log anything besides the solution
     #[argh(switch, short = 'q', long = "quiet")]
@@ -668,23 +1396,313 @@ struct Config {
     /// log intermediate states (these can be very large!)
     #[argh(switch, long = "log-states")]
     log_states: bool,
+
+    /// number of worker threads to search with (default: 1, i.e. single-threaded)
+    #[argh(option, long = "threads", default = "1")]
+    threads: usize,
+
+    /// skip re-exploring a branch whose table is identical to one already seen
+    #[argh(switch, long = "dedupe-tables")]
+    dedupe_tables: bool,
+
+    /// disable the arc-consistency propagation pass before each guess, falling back to pure
+    /// blind branching (see `Config::propagate`)
+    #[argh(switch, long = "no-propagate")]
+    no_propagate: bool,
+
+    /// parallel search strategy to use when --threads > 1: "work-queue" (default) or "fork-join"
+    #[argh(option, long = "parallel-strategy", default = "String::from(\"work-queue\")")]
+    parallel_strategy: String,
+
+    /// (fork-join strategy only) guess-tree depth below which branches still fork into separate
+    /// tasks, rather than recursing sequentially (default: 8)
+    #[argh(option, long = "fork-join-depth", default = "8")]
+    fork_join_depth: usize,
+
+    /// solve by compiling to CNF and handing it to an external SAT solver, instead of the
+    /// built-in search. Only reports a single solution.
+    #[argh(switch, long = "sat")]
+    sat: bool,
 }
 
 fn main() {
     let config = argh::from_env::<Config>();
 
+    if config.repl {
+        repl::run_interactive(config);
+        return;
+    }
+    let filename = config
+        .filename
+        .clone()
+        .unwrap_or_else(|| panic!("Expected a puzzle definition file, or --repl"));
+
     let parser = make_puzzle_parser().unwrap_or_else(|err| panic!("{}", err));
-    let file_contents = fs::read_to_string(&config.filename).unwrap();
+    let file_contents = fs::read_to_string(&filename).unwrap();
     let puzzle_definition = parser
-        .parse(&config.filename, &file_contents)
+        .parse(&filename, &file_contents)
         .unwrap_or_else(|err| panic!("{}", err));
 
+    let use_sat = config.sat;
     let mut solver = puzzle_definition
         .make_solver(config)
         .unwrap_or_else(|err| panic!("{}", err));
 
-    let solutions = solver.solve();
-    let count = solutions.0.len();
-    println!("Solutions:\n{}", solutions);
-    println!("{} solutions", count);
+    if use_sat {
+        match solver.solve_with_sat() {
+            Some(solution) => println!("Solution:\n{}", solution),
+            None => println!("No solutions"),
+        }
+    } else {
+        let solutions = solver.solve();
+        let count = solutions.0.len();
+        println!("Solutions:\n{}", solutions);
+        println!("{} solutions", count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expr_eval_letters_compare_as_their_magnitude() {
+        // Regression test for a bug where a letter literal's sign was compared directly against
+        // `elems` (which are always non-negative magnitudes after `.abs()`), so e.g. `a` (-1)
+        // never equaled a cell holding the number 1.
+        let a = read_letter('a').unwrap();
+        assert_eq!(RuleExpr::Eq(Box::new(RuleExpr::Int(a)), Box::new(RuleExpr::Int(1))).eval(&[]), Some(1));
+        assert_eq!(RuleExpr::Eq(Box::new(RuleExpr::Count(a)), Box::new(RuleExpr::Int(2))).eval(&[1, 1]), Some(1));
+    }
+
+    #[test]
+    fn test_expr_eval_aggregates() {
+        let elems = [1, 2, 3];
+        assert_eq!(RuleExpr::Sum.eval(&elems), Some(6));
+        assert_eq!(RuleExpr::Prod.eval(&elems), Some(6));
+        assert_eq!(RuleExpr::Min.eval(&elems), Some(1));
+        assert_eq!(RuleExpr::Max.eval(&elems), Some(3));
+        assert_eq!(RuleExpr::Count(2).eval(&elems), Some(1));
+        assert_eq!(RuleExpr::Sum.eval(&[]), Some(0));
+    }
+
+    #[test]
+    fn test_expr_eval_arithmetic_and_abs() {
+        let one = Box::new(RuleExpr::Int(1));
+        let two = Box::new(RuleExpr::Int(2));
+        assert_eq!(RuleExpr::Add(one.clone(), two.clone()).eval(&[]), Some(3));
+        assert_eq!(RuleExpr::Sub(one.clone(), two.clone()).eval(&[]), Some(-1));
+        assert_eq!(RuleExpr::Mul(one.clone(), two.clone()).eval(&[]), Some(2));
+        assert_eq!(RuleExpr::Abs(Box::new(RuleExpr::Sub(one, two))).eval(&[]), Some(1));
+    }
+
+    #[test]
+    fn test_expr_eval_div_mod_by_zero_is_none() {
+        let n = Box::new(RuleExpr::Int(4));
+        let zero = Box::new(RuleExpr::Int(0));
+        assert_eq!(RuleExpr::Div(n.clone(), zero.clone()).eval(&[]), None);
+        assert_eq!(RuleExpr::Mod(n, zero).eval(&[]), None);
+    }
+
+    #[test]
+    fn test_expr_eval_comparisons_and_booleans() {
+        let one = Box::new(RuleExpr::Int(1));
+        let two = Box::new(RuleExpr::Int(2));
+        assert_eq!(RuleExpr::Lt(one.clone(), two.clone()).eval(&[]), Some(1));
+        assert_eq!(RuleExpr::Gt(one.clone(), two.clone()).eval(&[]), Some(0));
+        assert_eq!(RuleExpr::Ne(one.clone(), two.clone()).eval(&[]), Some(1));
+        assert_eq!(
+            RuleExpr::And(one.clone(), two.clone()).eval(&[]),
+            Some(1)
+        );
+        assert_eq!(RuleExpr::Not(one).eval(&[]), Some(0));
+        assert_eq!(RuleExpr::Or(Box::new(RuleExpr::Int(0)), two).eval(&[]), Some(1));
+    }
+
+    #[test]
+    fn test_pattern_literal_symbols() {
+        let nfa = compile_pattern("ab").unwrap();
+        let a = read_letter('a').unwrap();
+        let b = read_letter('b').unwrap();
+        assert!(nfa.accepts(&[a, b]));
+        assert!(!nfa.accepts(&[b, a]));
+        assert!(!nfa.accepts(&[a])); // too short
+        assert!(!nfa.accepts(&[a, b, a])); // too long
+    }
+
+    #[test]
+    fn test_pattern_any_and_digit() {
+        let nfa = compile_pattern(".5").unwrap();
+        assert!(nfa.accepts(&[read_letter('z').unwrap(), 5]));
+        assert!(nfa.accepts(&[3, 5]));
+        assert!(!nfa.accepts(&[3, 6]));
+    }
+
+    #[test]
+    fn test_pattern_class_and_range() {
+        let nfa = compile_pattern("[a-c2]").unwrap();
+        assert!(nfa.accepts(&[read_letter('a').unwrap()]));
+        assert!(nfa.accepts(&[read_letter('c').unwrap()]));
+        assert!(nfa.accepts(&[2]));
+        assert!(!nfa.accepts(&[read_letter('d').unwrap()]));
+    }
+
+    #[test]
+    fn test_pattern_quantifiers() {
+        let a = read_letter('a').unwrap();
+        let b = read_letter('b').unwrap();
+
+        let star = compile_pattern("a*b").unwrap();
+        assert!(star.accepts(&[b]));
+        assert!(star.accepts(&[a, a, a, b]));
+        assert!(!star.accepts(&[a, a, a]));
+
+        let plus = compile_pattern("a+b").unwrap();
+        assert!(!plus.accepts(&[b]));
+        assert!(plus.accepts(&[a, b]));
+
+        let opt = compile_pattern("a?b").unwrap();
+        assert!(opt.accepts(&[b]));
+        assert!(opt.accepts(&[a, b]));
+        assert!(!opt.accepts(&[a, a, b]));
+    }
+
+    #[test]
+    fn test_pattern_compile_errors() {
+        assert!(compile_pattern("").is_err());
+        assert!(compile_pattern("[ab").is_err());
+        assert!(compile_pattern("!").is_err());
+    }
+
+    /// A `Config` with logging off and every other setting at its default, for driving
+    /// `PuzzleDefinition::make_solver` directly in a test.
+    fn test_config() -> Config {
+        Config {
+            filename: None,
+            repl: false,
+            quiet: true,
+            log_constraints: false,
+            log_completed: false,
+            log_elapsed: false,
+            log_states: false,
+            threads: 1,
+            dedupe_tables: false,
+            no_propagate: false,
+            parallel_strategy: "work-queue".to_string(),
+            fork_join_depth: 8,
+            sat: false,
+        }
+    }
+
+    /// A 2-cell puzzle, both cells ranging over `{1, 2}`, with a single rule set applying `rule`
+    /// over both cells. Returns the solutions' `Data::to_string()`s, as an unordered set.
+    fn solve_two_cells(rule: PuzzleRule) -> std::collections::HashSet<String> {
+        let def = PuzzleDefinition {
+            layout: "* *\n".to_string(),
+            ranges: vec![PuzzleRange {
+                possibilities: vec![1, 2],
+                data: "* *\n".to_string(),
+            }],
+            rule_sets: vec![PuzzleRuleSet {
+                rules: vec![rule],
+                datas: vec!["* *\n".to_string()],
+            }],
+            initial: None,
+        };
+        let mut solver = def.make_solver(test_config()).unwrap();
+        solver
+            .solve()
+            .0
+            .iter()
+            .map(|data| data.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_all_different_rule_rejects_equal_assignments() {
+        let solutions = solve_two_cells(PuzzleRule::AllDifferent);
+        let expected: std::collections::HashSet<String> =
+            ["1 2\n".to_string(), "2 1\n".to_string()].into_iter().collect();
+        assert_eq!(solutions, expected);
+    }
+
+    #[test]
+    fn test_all_same_rule_rejects_distinct_assignments() {
+        let solutions = solve_two_cells(PuzzleRule::AllSame);
+        let expected: std::collections::HashSet<String> =
+            ["1 1\n".to_string(), "2 2\n".to_string()].into_iter().collect();
+        assert_eq!(solutions, expected);
+    }
+
+    #[test]
+    fn test_distinct_rule_rejects_equal_assignments() {
+        // Same expected behavior as `AllDifferent` (see `test_all_different_rule_rejects_equal_assignments`),
+        // but exercised via the `Distinct` variant, which goes through the real `AllDifferent`
+        // constraint instead of a scan-based `Pred`.
+        let solutions = solve_two_cells(PuzzleRule::Distinct);
+        let expected: std::collections::HashSet<String> =
+            ["1 2\n".to_string(), "2 1\n".to_string()].into_iter().collect();
+        assert_eq!(solutions, expected);
+    }
+
+    #[test]
+    fn test_compare_rule_pairs_two_datas_positionally() {
+        // Two separate data templates, each selecting only one of the two cells, so the
+        // comparison rule pairs cell 0 up against cell 1 (rather than all cells against each
+        // other, the way sum/prod's single flattened var_list would).
+        let def = PuzzleDefinition {
+            layout: "* *\n".to_string(),
+            ranges: vec![PuzzleRange {
+                possibilities: vec![1, 2],
+                data: "* *\n".to_string(),
+            }],
+            rule_sets: vec![PuzzleRuleSet {
+                rules: vec![PuzzleRule::Compare(CompareOp::Lt)],
+                datas: vec!["1 .\n".to_string(), ". 2\n".to_string()],
+            }],
+            initial: None,
+        };
+        let mut solver = def.make_solver(test_config()).unwrap();
+        let solutions: std::collections::HashSet<String> = solver
+            .solve()
+            .0
+            .iter()
+            .map(|data| data.to_string())
+            .collect();
+        let expected: std::collections::HashSet<String> = ["1 2\n".to_string()].into_iter().collect();
+        assert_eq!(solutions, expected);
+    }
+
+    #[test]
+    fn test_compare_rule_pairs_two_letter_groups_by_key() {
+        // Each template has *two* letter-keyed groups, with the letters given in opposite order
+        // on each side ("A . B .\n" vs "B . A .\n"). `group_vars_by_key` has to pair up group 'A'
+        // from one template with group 'A' from the other (and likewise for 'B') based on the
+        // key itself, not on whatever order a `HashMap`'s iteration happens to produce -- that
+        // order isn't even guaranteed to agree between the left and right template's maps, which
+        // are built independently. Pairing by key here makes the rule require cell 2 < cell 0
+        // (group 'B') and cell 0 < cell 2 (group 'A') at once, which is unsatisfiable; if the
+        // groups were ever mismatched to the same side twice, this would stop being a
+        // contradiction and the puzzle would (wrongly) have solutions.
+        let def = PuzzleDefinition {
+            layout: "* * * *\n".to_string(),
+            ranges: vec![PuzzleRange {
+                possibilities: vec![1, 2],
+                data: "* * * *\n".to_string(),
+            }],
+            rule_sets: vec![PuzzleRuleSet {
+                rules: vec![PuzzleRule::Compare(CompareOp::Lt)],
+                datas: vec!["A . B .\n".to_string(), "B . A .\n".to_string()],
+            }],
+            initial: None,
+        };
+        let mut solver = def.make_solver(test_config()).unwrap();
+        let solutions: std::collections::HashSet<String> = solver
+            .solve()
+            .0
+            .iter()
+            .map(|data| data.to_string())
+            .collect();
+        assert_eq!(solutions, std::collections::HashSet::new());
+    }
 }
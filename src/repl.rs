@@ -0,0 +1,667 @@
+//! An interactive session for building up a puzzle definition one block at a time, instead of
+//! writing it all to a file up front: `layout`/`range`/`rule`/`initial` blocks are typed in using
+//! the *same* grammar `make_puzzle_parser` reads from a puzzle file (see `main::make_puzzle_parser`
+//! for the file-at-once form), plus `solve`/`load`/`quit` commands to drive the session. `solve`
+//! re-runs `Solvomatic::solve` over everything accumulated so far and prints the narrowed `Data`
+//! states, so a puzzle can be explored incrementally rather than re-invoking the binary per edit.
+//!
+//! The line editor is a `rustyline::Editor` backed by a `ReplHelper` that validates each block as
+//! it's typed (so a multi-line `layout`/`range`/`rule` block can span several lines before being
+//! submitted), highlights DSL keywords and `|`-prefixed template lines, and completes rule names
+//! after `rule`.
+
+use crate::{BadInput, Config, PuzzleDefinition, PuzzleRange, PuzzleRule, PuzzleRuleSet};
+use parser_ll1::{CompiledParser, Grammar, GrammarError, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::{CmdKind, Highlighter};
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Editor, Helper};
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+/************************
+ *     Grammar          *
+ ************************/
+
+/// One parsed REPL input: either a puzzle block to fold into the session's `ReplPuzzle`, or a
+/// bare command.
+#[derive(Debug, Clone)]
+enum ReplCommand {
+    Block(ReplBlock),
+    Solve,
+    Load(String),
+}
+
+#[derive(Debug, Clone)]
+enum ReplBlock {
+    Layout(String),
+    Range(PuzzleRange),
+    RuleSet(PuzzleRuleSet),
+    Initial(String),
+}
+
+/// Compiles a parser for one REPL input: a `layout`/`range`/`rule`/`initial` block, or a
+/// `solve`/`load PATH` command.
+///
+/// This mirrors the block grammar in `main::make_puzzle_parser` line for line -- `parser_ll1`'s
+/// combinator values don't have a type that can be named and shared between two functions without
+/// fighting `impl Trait` rules, and `make_puzzle_parser` parses a whole file in one pass while this
+/// compiles the very same blocks so they can be typed one at a time. Keep the two in sync by hand
+/// if the puzzle DSL's grammar changes.
+fn make_repl_parser() -> Result<impl CompiledParser<ReplCommand>, GrammarError> {
+    use crate::{read_letter, CompareOp, RuleExpr};
+    use parser_ll1::{choice, tuple};
+
+    let mut g = Grammar::with_whitespace("([ \t\n]+|#[^\n]*\n)+")?;
+
+    let data_p = g.regex("template", "(\\|[^\n]*\n)+")?.span(|span| {
+        let mut stripped = String::new();
+        for line in span.substr.lines() {
+            stripped.push_str(&line[1..]);
+            stripped.push('\n');
+        }
+        stripped
+    });
+
+    let letter_p = g
+        .regex("letter", "[a-zA-Z]")?
+        .span(|span| read_letter(span.substr.chars().next().unwrap()).unwrap());
+    let numeral_p = g
+        .regex("numeral", "[0-9]+")?
+        .try_span(|span| i32::from_str(span.substr));
+    let entry_p = choice("letter or numeral", (letter_p, numeral_p));
+
+    let entry_range_p = tuple(
+        "letter/numeral range",
+        (
+            entry_p.clone(),
+            entry_p.clone().preceded(g.string("..")?).opt(),
+        ),
+    )
+    .map(|(a, opt_b)| {
+        if let Some(b) = opt_b {
+            let min = a.min(b);
+            let max = a.max(b);
+            (min..=max).collect::<Vec<i32>>()
+        } else {
+            vec![a]
+        }
+    });
+    let entry_set_p = entry_range_p
+        .clone()
+        .fold_many1(entry_range_p, |mut vec1, vec2| {
+            vec1.extend(vec2);
+            vec1
+        });
+
+    let layout_p = tuple("layout", (g.string("layout")?, data_p.clone()))
+        .map(|(_, data)| ReplBlock::Layout(data));
+
+    let range_p = entry_set_p.clone().preceded(g.string("range")?);
+    let range_and_data_p = tuple("range", (range_p, data_p.clone()))
+        .map(|(possibilities, data)| ReplBlock::Range(PuzzleRange { possibilities, data }));
+
+    // A filesystem path, or an `http(s)://` URL (see `main::WordListLoader::load`).
+    let path = g
+        .regex("path", "[A-Za-z0-9_./:?=&%~+-]+")?
+        .span(|span| span.substr.to_owned());
+    let sum_p = entry_p
+        .clone()
+        .preceded(g.string("sum")?)
+        .map(PuzzleRule::Sum);
+    let prod_p = entry_p
+        .clone()
+        .preceded(g.string("prod")?)
+        .map(PuzzleRule::Prod);
+    let permutation_p = tuple(
+        "permutation rule",
+        (g.string("permutation")?, entry_set_p.clone()),
+    )
+    .map(|(_, entries)| PuzzleRule::Permutation(entries));
+    let subset_p = tuple("subset rule", (g.string("subset")?, entry_set_p.clone()))
+        .map(|(_, entries)| PuzzleRule::Subset(entries));
+    let superset_p = tuple(
+        "superset rule",
+        (g.string("superset")?, entry_set_p.clone()),
+    )
+    .map(|(_, entries)| PuzzleRule::Superset(entries));
+    let word_p = path
+        .clone()
+        .preceded(g.string("word")?)
+        .map(PuzzleRule::Word);
+    let in_order_p = g.string("in_order")?.constant(PuzzleRule::InOrder(true));
+    let in_reverse_order_p = g
+        .string("in_reverse_order")?
+        .constant(PuzzleRule::InOrder(false));
+    let distinct_p = g.string("distinct")?.constant(PuzzleRule::Distinct);
+    let diff_p = g.string("diff")?.constant(PuzzleRule::Distinct);
+    let all_different_p = g
+        .string("all_different")?
+        .constant(PuzzleRule::AllDifferent);
+    let all_same_p = g.string("all_same")?.constant(PuzzleRule::AllSame);
+    let lt_p = g.string("lt")?.constant(PuzzleRule::Compare(CompareOp::Lt));
+    let le_p = g.string("le")?.constant(PuzzleRule::Compare(CompareOp::Le));
+    let eq_p = g.string("eq")?.constant(PuzzleRule::Compare(CompareOp::Eq));
+    let ne_p = g.string("ne")?.constant(PuzzleRule::Compare(CompareOp::Ne));
+
+    // `expr EXPR`: mirrors `main::make_puzzle_parser`'s expression sub-grammar (see `RuleExpr`).
+    let agg_p = choice(
+        "aggregate ('sum', 'prod', 'min', 'max')",
+        (
+            g.string("sum")?.constant(RuleExpr::Sum),
+            g.string("prod")?.constant(RuleExpr::Prod),
+            g.string("min")?.constant(RuleExpr::Min),
+            g.string("max")?.constant(RuleExpr::Max),
+        ),
+    );
+    let count_p = tuple(
+        "count(v)",
+        (g.string("count")?, g.string("(")?, entry_p.clone(), g.string(")")?),
+    )
+    .map(|(_, _, v, _)| RuleExpr::Count(v));
+    let simple_p = choice("expression atom", (count_p, agg_p, entry_p.clone().map(RuleExpr::Int)));
+    let abs_p = tuple(
+        "abs(...)",
+        (g.string("abs")?, g.string("(")?, simple_p.clone(), g.string(")")?),
+    )
+    .map(|(_, _, e, _)| RuleExpr::Abs(Box::new(e)));
+    let atom_p = choice("expression atom", (abs_p, simple_p));
+
+    let mul_op_p = choice(
+        "'*', '/', or '%'",
+        (
+            g.string("*")?.constant(RuleExpr::Mul as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("/")?.constant(RuleExpr::Div as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("%")?.constant(RuleExpr::Mod as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+        ),
+    );
+    let mul_rhs_p = tuple("multiplicative op and operand", (mul_op_p, atom_p.clone()))
+        .many1()
+        .opt();
+    let mul_p = tuple("multiplicative expression", (atom_p, mul_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, (op, rhs)| op(Box::new(acc), Box::new(rhs)))
+    });
+
+    let add_op_p = choice(
+        "'+' or '-'",
+        (
+            g.string("+")?.constant(RuleExpr::Add as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("-")?.constant(RuleExpr::Sub as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+        ),
+    );
+    let add_rhs_p = tuple("additive op and operand", (add_op_p, mul_p.clone())).many1().opt();
+    let add_p = tuple("additive expression", (mul_p, add_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, (op, rhs)| op(Box::new(acc), Box::new(rhs)))
+    });
+
+    let cmp_op_p = choice(
+        "'=', '!=', '<', '<=', '>', or '>='",
+        (
+            g.string("!=")?.constant(RuleExpr::Ne as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("<=")?.constant(RuleExpr::Le as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string(">=")?.constant(RuleExpr::Ge as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("=")?.constant(RuleExpr::Eq as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string("<")?.constant(RuleExpr::Lt as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+            g.string(">")?.constant(RuleExpr::Gt as fn(Box<RuleExpr>, Box<RuleExpr>) -> RuleExpr),
+        ),
+    );
+    let cmp_rhs_p = tuple("comparison op and operand", (cmp_op_p, add_p.clone())).opt();
+    let cmp_p = tuple("comparison expression", (add_p, cmp_rhs_p)).map(|(first, opt_rhs)| {
+        match opt_rhs {
+            Some((op, rhs)) => op(Box::new(first), Box::new(rhs)),
+            None => first,
+        }
+    });
+
+    let not_p = choice(
+        "expression",
+        (
+            cmp_p.clone().preceded(g.string("not")?).map(|e| RuleExpr::Not(Box::new(e))),
+            cmp_p,
+        ),
+    );
+    let and_rhs_p = not_p.clone().preceded(g.string("and")?).many1().opt();
+    let and_p = tuple("'and' expression", (not_p, and_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, rhs| RuleExpr::And(Box::new(acc), Box::new(rhs)))
+    });
+    let or_rhs_p = and_p.clone().preceded(g.string("or")?).many1().opt();
+    let or_p = tuple("'or' expression", (and_p, or_rhs_p)).map(|(first, rest)| {
+        rest.unwrap_or_default()
+            .into_iter()
+            .fold(first, |acc, rhs| RuleExpr::Or(Box::new(acc), Box::new(rhs)))
+    });
+    let expr_p = or_p.preceded(g.string("expr")?).map(PuzzleRule::Expr);
+
+    // `pattern PATTERN` / `regex PATTERN`: mirrors `main::make_puzzle_parser`'s pattern grammar
+    // (see `main::compile_pattern`).
+    let pattern_str_p = g
+        .regex("pattern", "[-a-zA-Z0-9.\\[\\]*+?]+")?
+        .span(|span| span.substr.to_owned());
+    let pattern_p = pattern_str_p
+        .clone()
+        .preceded(g.string("pattern")?)
+        .map(PuzzleRule::Pattern);
+    let regex_p = pattern_str_p
+        .preceded(g.string("regex")?)
+        .map(PuzzleRule::Pattern);
+    let pattern_or_regex_p = choice("rule name", (pattern_p, regex_p));
+
+    let ne_and_beyond_p = choice("rule name", (ne_p, choice("rule name", (expr_p, pattern_or_regex_p))));
+    let rule_p = choice(
+        "rule name ('sum', 'prod', 'word', 'permutation', 'subset', 'superset', 'in_order', \
+         'in_reverse_order', 'distinct', 'diff', 'all_different', 'all_same', 'lt', 'le', 'eq', \
+         'ne', 'expr', 'pattern', 'regex')",
+        (
+            choice("rule name", (sum_p, prod_p, word_p, permutation_p)),
+            choice("rule name", (subset_p, superset_p, in_order_p, in_reverse_order_p)),
+            choice("rule name", (distinct_p, diff_p, all_different_p, all_same_p)),
+            choice("rule name", (lt_p, le_p, eq_p, ne_and_beyond_p)),
+        ),
+    );
+    let rules_p = rule_p.preceded(g.string("rule")?).many1();
+    let rule_set_p = tuple("rules", (rules_p, data_p.clone().many1()))
+        .map(|(rules, datas)| ReplBlock::RuleSet(PuzzleRuleSet { rules, datas }));
+
+    let initial_p = tuple("initial", (g.string("initial")?, data_p))
+        .map(|(_, data)| ReplBlock::Initial(data));
+
+    let block_p = choice(
+        "a 'layout'/'range'/'rule'/'initial' block",
+        (layout_p, range_and_data_p, rule_set_p, initial_p),
+    )
+    .map(ReplCommand::Block);
+
+    let solve_p = g.string("solve")?.constant(ReplCommand::Solve);
+    let load_p = path.preceded(g.string("load")?).map(ReplCommand::Load);
+
+    let command_p = choice(
+        "a puzzle block, 'solve', or 'load PATH'",
+        (block_p, solve_p, load_p),
+    );
+
+    g.compile_parser(command_p)
+}
+
+/// `parser_ll1` reports running out of input as part of its error message (e.g. "... but found
+/// end of file"); treat only that shape as "needs more lines", and anything else as a genuine
+/// syntax error that shouldn't keep prompting for more input.
+fn looks_incomplete(message: &str) -> bool {
+    message.contains("end of file") || message.contains("end of input")
+}
+
+/************************
+ *     Puzzle state     *
+ ************************/
+
+/// The puzzle being assembled by the REPL's `layout`/`range`/`rule`/`initial` blocks, mirroring
+/// `PuzzleDefinition` but filled in one command at a time instead of parsed from a whole file.
+#[derive(Debug, Default)]
+struct ReplPuzzle {
+    layout: Option<String>,
+    ranges: Vec<PuzzleRange>,
+    rule_sets: Vec<PuzzleRuleSet>,
+    initial: Option<String>,
+}
+
+impl ReplPuzzle {
+    fn add(&mut self, block: ReplBlock) {
+        match block {
+            ReplBlock::Layout(layout) => self.layout = Some(layout),
+            ReplBlock::Range(range) => self.ranges.push(range),
+            ReplBlock::RuleSet(rule_set) => self.rule_sets.push(rule_set),
+            ReplBlock::Initial(initial) => self.initial = Some(initial),
+        }
+    }
+
+    fn to_definition(&self) -> Result<PuzzleDefinition, ReplError> {
+        Ok(PuzzleDefinition {
+            layout: self.layout.clone().ok_or(ReplError::NoLayout)?,
+            ranges: self.ranges.clone(),
+            rule_sets: self.rule_sets.clone(),
+            initial: self.initial.clone(),
+        })
+    }
+}
+
+/************************
+ *     Errors           *
+ ************************/
+
+#[derive(Debug)]
+enum ReplError {
+    Parse(String),
+    /// `solve` was run before any `layout` block was given.
+    NoLayout,
+    BadInput(BadInput),
+    Io(String, String),
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplError::Parse(msg) => write!(f, "{}", msg),
+            ReplError::NoLayout => write!(f, "No 'layout' block has been given yet"),
+            ReplError::BadInput(err) => write!(f, "{}", err),
+            ReplError::Io(path, msg) => write!(f, "Couldn't read '{}': {}", path, msg),
+        }
+    }
+}
+
+/************************
+ *     Evaluator        *
+ ************************/
+
+/// The REPL's persistent state: the puzzle built up by `layout`/`range`/`rule`/`initial` blocks
+/// (or loaded wholesale with `load`), and the `Config` each `solve` rebuilds a fresh
+/// `Solvomatic` with.
+struct Repl {
+    config: Config,
+    puzzle: ReplPuzzle,
+}
+
+impl Repl {
+    fn new(config: Config) -> Repl {
+        Repl {
+            config,
+            puzzle: ReplPuzzle::default(),
+        }
+    }
+
+    fn run_command(&mut self, command: ReplCommand) -> Result<Option<String>, ReplError> {
+        match command {
+            ReplCommand::Block(block) => {
+                self.puzzle.add(block);
+                Ok(None)
+            }
+            ReplCommand::Solve => {
+                let definition = self.puzzle.to_definition()?;
+                let mut solver = definition
+                    .make_solver(self.config.clone())
+                    .map_err(ReplError::BadInput)?;
+                Ok(Some(solver.solve().to_string()))
+            }
+            ReplCommand::Load(path) => {
+                let contents = fs::read_to_string(&path)
+                    .map_err(|err| ReplError::Io(path.clone(), err.to_string()))?;
+                let parser = crate::make_puzzle_parser()
+                    .unwrap_or_else(|err| panic!("solvomatic grammar is broken: {}", err));
+                let definition = parser
+                    .parse(&path, &contents)
+                    .map_err(|err| ReplError::Parse(err.to_string()))?;
+                self.puzzle = ReplPuzzle {
+                    layout: Some(definition.layout),
+                    ranges: definition.ranges,
+                    rule_sets: definition.rule_sets,
+                    initial: definition.initial,
+                };
+                Ok(None)
+            }
+        }
+    }
+}
+
+/************************
+ *     Line editor      *
+ ************************/
+
+/// DSL keywords `Highlighter` colors. `Completer` offers only the `RULE_NAMES` subset, after
+/// `rule`.
+const KEYWORDS: &[&str] = &[
+    "layout",
+    "range",
+    "rule",
+    "initial",
+    "sum",
+    "prod",
+    "word",
+    "permutation",
+    "subset",
+    "superset",
+    "in_order",
+    "in_reverse_order",
+    "distinct",
+    "diff",
+    "all_different",
+    "all_same",
+    "lt",
+    "le",
+    "eq",
+    "ne",
+    "expr",
+    "min",
+    "max",
+    "count",
+    "abs",
+    "and",
+    "or",
+    "not",
+    "pattern",
+    "regex",
+    "solve",
+    "load",
+];
+
+const RULE_NAMES: &[&str] = &[
+    "sum",
+    "prod",
+    "word",
+    "permutation",
+    "subset",
+    "superset",
+    "in_order",
+    "in_reverse_order",
+    "distinct",
+    "diff",
+    "all_different",
+    "all_same",
+    "lt",
+    "le",
+    "eq",
+    "ne",
+    "expr",
+    "pattern",
+    "regex",
+];
+
+/// `rustyline::Helper` for the puzzle DSL: validates a block across however many lines it takes
+/// to parse (see `make_repl_parser`), highlights keywords and `|` template lines, and completes
+/// rule names after `rule`.
+struct ReplHelper {
+    parser: Box<dyn CompiledParser<ReplCommand>>,
+}
+
+impl ReplHelper {
+    fn new() -> Result<ReplHelper, GrammarError> {
+        Ok(ReplHelper {
+            parser: Box::new(make_repl_parser()?),
+        })
+    }
+}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let input = ctx.input();
+        if input.trim().is_empty() || input.trim() == "quit" || input.trim() == "exit" {
+            return Ok(ValidationResult::Valid(None));
+        }
+        match self.parser.parse("<repl>", input) {
+            Ok(_) => Ok(ValidationResult::Valid(None)),
+            Err(err) => {
+                let message = err.to_string();
+                if looks_incomplete(&message) {
+                    Ok(ValidationResult::Incomplete)
+                } else {
+                    Ok(ValidationResult::Invalid(Some(format!("  {}", message))))
+                }
+            }
+        }
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let mut out = String::with_capacity(line.len());
+        let mut in_template = false;
+        for word in line.split_inclusive(|ch: char| ch.is_whitespace()) {
+            let trimmed = word.trim_end_matches(char::is_whitespace);
+            let trailing = &word[trimmed.len()..];
+            if trimmed.starts_with('|') {
+                in_template = true;
+            }
+            if in_template {
+                out.push_str("\x1b[36m"); // cyan: a template/data line
+                out.push_str(trimmed);
+                out.push_str("\x1b[0m");
+            } else if KEYWORDS.contains(&trimmed) {
+                out.push_str("\x1b[33m"); // yellow: a DSL keyword
+                out.push_str(trimmed);
+                out.push_str("\x1b[0m");
+            } else {
+                out.push_str(trimmed);
+            }
+            out.push_str(trailing);
+            if trailing.contains('\n') {
+                in_template = false;
+            }
+        }
+        Cow::Owned(out)
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, _kind: CmdKind) -> bool {
+        true
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+
+    fn hint(&self, _line: &str, _pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        None
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let word_start = line[..pos]
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| i + 1);
+        let word = &line[word_start..pos];
+        // Only offer rule-name completions right after a `rule` keyword (not merely after some
+        // other token that happens to end in "rule", e.g. a `word` rule's path argument).
+        let prev_token = line[..word_start]
+            .trim_end()
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or("");
+        if prev_token != "rule" {
+            return Ok((pos, Vec::new()));
+        }
+        let candidates = RULE_NAMES
+            .iter()
+            .filter(|name| name.starts_with(word))
+            .map(|name| Pair {
+                display: (*name).to_owned(),
+                replacement: (*name).to_owned(),
+            })
+            .collect();
+        Ok((word_start, candidates))
+    }
+}
+
+impl Helper for ReplHelper {}
+
+/// Run an interactive line-editing session over the puzzle DSL (history, multi-line block
+/// editing, highlighting, rule-name completion), until the user types `quit`/`exit` or sends EOF
+/// (Ctrl-D).
+pub fn run_interactive(config: Config) {
+    let mut repl = Repl::new(config);
+    let helper =
+        ReplHelper::new().unwrap_or_else(|err| panic!("solvomatic grammar is broken: {}", err));
+    let mut editor: Editor<ReplHelper, FileHistory> =
+        Editor::new().expect("Failed to start line editor");
+    editor.set_helper(Some(helper));
+
+    loop {
+        match editor.readline("solvomatic> ") {
+            Ok(block) => {
+                if block.trim().is_empty() {
+                    continue;
+                }
+                let _ = editor.add_history_entry(block.as_str());
+                if block.trim() == "quit" || block.trim() == "exit" {
+                    break;
+                }
+                // Parse the block as submitted (not trimmed): `data_p`'s template lines require a
+                // trailing newline after the last `|...` line, which `validate` saw and accepted.
+                let helper = editor.helper().expect("REPL helper was set at startup");
+                let result = match helper.parser.parse("<repl>", &block) {
+                    Ok(command) => repl.run_command(command),
+                    Err(err) => Err(ReplError::Parse(err.to_string())),
+                };
+                match result {
+                    Ok(Some(output)) => println!("{}", output),
+                    Ok(None) => (),
+                    Err(err) => eprintln!("{}", err),
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                eprintln!("{}", err);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_incomplete() {
+        assert!(looks_incomplete("parse error: expected ... but found end of file"));
+        assert!(looks_incomplete("parse error: expected ... but found end of input"));
+        assert!(!looks_incomplete("parse error: expected 'sum', found 'pord'"));
+    }
+
+    #[test]
+    fn test_parse_solve_command() {
+        let parser = make_repl_parser().unwrap();
+        let command = parser.parse("<repl>", "solve").unwrap();
+        assert!(matches!(command, ReplCommand::Solve));
+    }
+
+    #[test]
+    fn test_parse_load_command() {
+        let parser = make_repl_parser().unwrap();
+        let command = parser.parse("<repl>", "load puzzles/foo.txt").unwrap();
+        match command {
+            ReplCommand::Load(path) => assert_eq!(path, "puzzles/foo.txt"),
+            other => panic!("expected ReplCommand::Load, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        let parser = make_repl_parser().unwrap();
+        assert!(parser.parse("<repl>", "not a valid command").is_err());
+    }
+}
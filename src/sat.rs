@@ -0,0 +1,91 @@
+//! CNF formula-building for `Solvomatic::to_cnf`/`solve_with_sat`: a scalable alternative to the
+//! crate's own enumerate-and-guess search for large instances, by compiling the problem down to
+//! DIMACS CNF and handing it to an external SAT solver.
+
+/// A CNF clause: a disjunction of literals, each a 1-based DIMACS variable number (negative for
+/// negation).
+pub type Clause = Vec<i64>;
+
+/// A CNF formula under construction.
+#[derive(Debug, Default, Clone)]
+pub struct Cnf {
+    pub clauses: Vec<Clause>,
+    next_var: i64,
+}
+
+impl Cnf {
+    pub fn new() -> Cnf {
+        Cnf {
+            clauses: Vec::new(),
+            next_var: 1,
+        }
+    }
+
+    /// Allocate a fresh boolean variable, returning its 1-based DIMACS number.
+    pub fn fresh_var(&mut self) -> i64 {
+        let var = self.next_var;
+        self.next_var += 1;
+        var
+    }
+
+    pub fn add_clause(&mut self, clause: Clause) {
+        self.clauses.push(clause);
+    }
+
+    /// Exactly one of `lits` is true: an at-least-one clause over all of them, plus a pairwise
+    /// at-most-one clause for every pair.
+    pub fn exactly_one(&mut self, lits: &[i64]) {
+        self.add_clause(lits.to_vec());
+        for i in 0..lits.len() {
+            for &b in &lits[i + 1..] {
+                self.add_clause(vec![-lits[i], -b]);
+            }
+        }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        (self.next_var - 1) as usize
+    }
+
+    /// Render as DIMACS CNF text (the `p cnf <vars> <clauses>` format most SAT solvers accept).
+    pub fn to_dimacs(&self) -> String {
+        let mut out = format!("p cnf {} {}\n", self.num_vars(), self.clauses.len());
+        for clause in &self.clauses {
+            for lit in clause {
+                out.push_str(&lit.to_string());
+                out.push(' ');
+            }
+            out.push_str("0\n");
+        }
+        out
+    }
+}
+
+/// A `Cnf` formula together with the mapping `Solvomatic::to_cnf` used to build it: `literals[v][e]`
+/// is the literal meaning "variable `v` (by position in the table) takes its `e`-th remaining
+/// candidate value".
+pub struct Encoding {
+    pub cnf: Cnf,
+    pub literals: Vec<Vec<i64>>,
+}
+
+#[test]
+fn test_exactly_one() {
+    let mut cnf = Cnf::new();
+    let a = cnf.fresh_var();
+    let b = cnf.fresh_var();
+    let c = cnf.fresh_var();
+    cnf.exactly_one(&[a, b, c]);
+
+    assert_eq!(cnf.clauses, vec![vec![a, b, c], vec![-a, -b], vec![-a, -c], vec![-b, -c]]);
+}
+
+#[test]
+fn test_to_dimacs() {
+    let mut cnf = Cnf::new();
+    let a = cnf.fresh_var();
+    let b = cnf.fresh_var();
+    cnf.add_clause(vec![a, -b]);
+
+    assert_eq!(cnf.to_dimacs(), "p cnf 2 1\n1 -2 0\n");
+}
@@ -16,6 +16,15 @@ pub trait State: Display + 'static {
     fn new(metadata: &Self::MetaData) -> Self;
 
     fn set(&mut self, var: Self::Var, val: Self::Value);
+
+    /// Opt `Value` into the bitset-backed domain representation (see `Domain`) by returning a
+    /// small index (under 128, distinct for distinct values) for each value, or `None` if the
+    /// value isn't bitset-eligible. `Table::add_column`
+    /// uses this to pick the domain representation for each variable. The default never opts in,
+    /// which is always correct, just not maximally fast.
+    fn value_index(_value: &Self::Value) -> Option<usize> {
+        None
+    }
 }
 
 /// A bunch of states. This type exists solely for its `Display` method, which will show all of its
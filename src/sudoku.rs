@@ -0,0 +1,194 @@
+//! A loader for the compact text encodings Sudokus are usually shared in — either one line of
+//! `N*N` characters, or `N` lines of `N` characters each — so a puzzle from the usual corpus can
+//! be dropped in as a string instead of hand-writing the `var`/`Permutation`/`Pred` wiring (and
+//! the `prefilled` table) `examples/sudoku.rs` used to write out by hand. Blanks are `.`, `_`, or
+//! `0`; givens are digits `1`-`9` and, for grids bigger than 9x9, letters `A`-`Z` (`10`-`35`).
+//!
+//! Builds directly on [`Grid`], so it isn't limited to classic 9x9 Sudoku: `N` just needs to be a
+//! perfect square, so 4x4 and 16x16 variants work the same way.
+
+use crate::constraints::{Permutation, Pred};
+use crate::{Grid, Solvomatic};
+use std::fmt;
+
+/// Why [`load`] couldn't parse its input.
+#[derive(Debug)]
+pub enum SudokuError {
+    /// `N` has no well-defined block size, since it isn't a perfect square.
+    NotASquareSize(usize),
+    /// The input doesn't have exactly `expected` cells (ignoring blank lines), or — for a
+    /// multi-line input — one of its lines isn't `expected` characters long.
+    WrongLength { expected: usize, found: usize },
+    /// A character that's neither a blank marker, a digit, nor a letter.
+    BadChar(char),
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SudokuError::NotASquareSize(n) => {
+                write!(f, "{} is not a perfect square, so it has no block size", n)
+            }
+            SudokuError::WrongLength { expected, found } => {
+                write!(f, "expected {} cells, found {}", expected, found)
+            }
+            SudokuError::BadChar(ch) => {
+                write!(f, "'{}' is not a blank marker, digit, or letter", ch)
+            }
+        }
+    }
+}
+
+/// The side length of each of `N`'s square blocks (3 for 9, 4 for 16, 2 for 4), or an error if `N`
+/// isn't itself a perfect square.
+fn block_size(n: usize) -> Result<usize, SudokuError> {
+    let block = (n as f64).sqrt().round() as usize;
+    if block * block == n {
+        Ok(block)
+    } else {
+        Err(SudokuError::NotASquareSize(n))
+    }
+}
+
+/// A blank cell (`None`), or the value a given digit/letter character represents.
+fn parse_char(ch: char) -> Result<Option<u8>, SudokuError> {
+    match ch {
+        '.' | '_' | '0' => Ok(None),
+        '1'..='9' => Ok(Some(ch as u8 - b'0')),
+        'A'..='Z' => Ok(Some(ch as u8 - b'A' + 10)),
+        'a'..='z' => Ok(Some(ch as u8 - b'a' + 10)),
+        other => Err(SudokuError::BadChar(other)),
+    }
+}
+
+/// Parse `input` as either a single line of `n * n` characters, or `n` lines of `n` characters
+/// each, returning the non-blank cells as `(row, col, value)`.
+fn parse_givens(input: &str, n: usize) -> Result<Vec<(usize, usize, u8)>, SudokuError> {
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+
+    let chars = if lines.len() == 1 {
+        lines[0].chars().collect::<Vec<_>>()
+    } else {
+        for line in &lines {
+            let len = line.chars().count();
+            if len != n {
+                return Err(SudokuError::WrongLength {
+                    expected: n,
+                    found: len,
+                });
+            }
+        }
+        lines.into_iter().flat_map(|line| line.chars()).collect()
+    };
+
+    if chars.len() != n * n {
+        return Err(SudokuError::WrongLength {
+            expected: n * n,
+            found: chars.len(),
+        });
+    }
+
+    let mut givens = Vec::new();
+    for (i, ch) in chars.into_iter().enumerate() {
+        if let Some(value) = parse_char(ch)? {
+            givens.push((i / n, i % n, value));
+        }
+    }
+    Ok(givens)
+}
+
+/// Build a ready-to-solve `Solvomatic` from a standard Sudoku text encoding: `N * N` cells each
+/// 1..=N, the three `Permutation` families (rows, columns, and `block_size(N) x block_size(N)`
+/// blocks), and a `Pred` given for every filled-in cell.
+///
+/// `N` must be a perfect square (4, 9, 16, ...); `input` must have exactly `N * N` non-blank-line
+/// characters, either all on one line or spread `N` to a line.
+pub fn load<const N: usize>(
+    input: &str,
+    format_cell: fn(Option<&u8>) -> String,
+) -> Result<Solvomatic<Grid<N, N, u8>>, SudokuError> {
+    let block = block_size(N)?;
+    let givens = parse_givens(input, N)?;
+
+    let mut solver = Solvomatic::<Grid<N, N, u8>>::new(format_cell);
+
+    for cell in Grid::<N, N, u8>::cells() {
+        solver.var(cell, 1..=(N as u8));
+    }
+    for row in Grid::<N, N, u8>::rows() {
+        solver.constraint(row, Permutation::new(1..=(N as u8)));
+    }
+    for col in Grid::<N, N, u8>::cols() {
+        solver.constraint(col, Permutation::new(1..=(N as u8)));
+    }
+    for block_row in 0..block {
+        for block_col in 0..block {
+            solver.constraint(
+                Grid::<N, N, u8>::block(block_row, block_col, block, block),
+                Permutation::new(1..=(N as u8)),
+            );
+        }
+    }
+    for (row, col, n) in givens {
+        solver.constraint([(row, col)], Pred::new(move |[x]| *x == n));
+    }
+
+    Ok(solver)
+}
+
+#[test]
+fn test_block_size() {
+    assert_eq!(block_size(4).unwrap(), 2);
+    assert_eq!(block_size(9).unwrap(), 3);
+    assert_eq!(block_size(16).unwrap(), 4);
+    assert!(matches!(block_size(10), Err(SudokuError::NotASquareSize(10))));
+}
+
+#[test]
+fn test_parse_givens_single_line() {
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    assert_eq!(PUZZLE.len(), 81);
+
+    let too_long = format!("{}9", PUZZLE);
+    assert!(matches!(
+        parse_givens(&too_long, 9),
+        Err(SudokuError::WrongLength { expected: 81, found: 82 })
+    ));
+
+    let givens = parse_givens(PUZZLE, 9).unwrap();
+    assert!(givens.contains(&(0, 0, 5)));
+    assert!(givens.contains(&(0, 1, 3)));
+    assert!(givens.contains(&(1, 0, 6)));
+    assert_eq!(givens.len(), 81 - PUZZLE.chars().filter(|ch| *ch == '.').count());
+}
+
+#[test]
+fn test_parse_givens_multi_line() {
+    let givens = parse_givens("53..\n6..1\n.98.\n...6", 4).unwrap();
+    assert!(givens.contains(&(0, 0, 5)));
+    assert!(givens.contains(&(0, 1, 3)));
+    assert!(givens.contains(&(3, 3, 6)));
+    assert_eq!(givens.len(), 7);
+
+    // A ragged line (only 3 characters, not 4) is rejected.
+    let ragged = parse_givens("53..\n6..\n.98.\n...6", 4);
+    assert!(matches!(
+        ragged,
+        Err(SudokuError::WrongLength { expected: 4, found: 3 })
+    ));
+}
+
+#[test]
+fn test_parse_char() {
+    assert_eq!(parse_char('.').unwrap(), None);
+    assert_eq!(parse_char('_').unwrap(), None);
+    assert_eq!(parse_char('0').unwrap(), None);
+    assert_eq!(parse_char('5').unwrap(), Some(5));
+    assert_eq!(parse_char('A').unwrap(), Some(10));
+    assert!(matches!(parse_char('!'), Err(SudokuError::BadChar('!'))));
+}
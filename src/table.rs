@@ -1,4 +1,5 @@
 use crate::constraints::{Constraint, YesNoMaybe};
+use crate::domain::Domain;
 use crate::state::State;
 use std::fmt;
 
@@ -9,7 +10,17 @@ pub struct Table<S: State> {
     /// VarIndex -> Var
     pub vars: Vec<S::Var>,
     /// VarIndex -> set of Value
-    pub entries: Vec<Vec<S::Value>>,
+    pub entries: Vec<Domain<S::Value>>,
+    /// Index into `Solvomatic::constraints` -> whether that constraint is still worth
+    /// re-checking on this table. Once a constraint evaluates to `Yes` on a table, it stays
+    /// `Yes` on that table and everything derived from it (`guess` only ever shrinks domains),
+    /// so it's marked dead here and skipped by future `simplify_table` passes. `guess`'s `clone`
+    /// carries the set to each branch, and killing it on one branch doesn't affect siblings.
+    pub live_constraints: Vec<bool>,
+    /// The sequence of (var, value) branch decisions `guess()` made to arrive at this table,
+    /// oldest first. Recorded as a no-good (see `Solvomatic::no_goods`) if this table turns out
+    /// unsatisfiable, so sibling branches that would repeat the same dead end can be skipped.
+    pub trail: Vec<(VarIndex, S::Value)>,
 }
 
 // #derive doesn't work here; it inappropriately requires S: Clone
@@ -18,6 +29,8 @@ impl<S: State> Clone for Table<S> {
         Table {
             vars: self.vars.clone(),
             entries: self.entries.clone(),
+            live_constraints: self.live_constraints.clone(),
+            trail: self.trail.clone(),
         }
     }
 }
@@ -27,16 +40,44 @@ impl<S: State> Table<S> {
         Table {
             vars: Vec::new(),
             entries: Vec::new(),
+            live_constraints: Vec::new(),
+            trail: Vec::new(),
         }
     }
 
+    /// Size `live_constraints` to match the solver's constraint list, marking every constraint
+    /// live. A no-op once this table (or an ancestor it was cloned from) has already been sized,
+    /// so it's safe to call on every `simplify_table` pass.
+    pub fn init_live_constraints(&mut self, num_constraints: usize) {
+        if self.live_constraints.len() != num_constraints {
+            self.live_constraints = vec![true; num_constraints];
+        }
+    }
+
+    pub fn is_constraint_live(&self, index: usize) -> bool {
+        self.live_constraints[index]
+    }
+
+    pub fn kill_constraint(&mut self, index: usize) {
+        self.live_constraints[index] = false;
+    }
+
     pub fn add_column(&mut self, var: S::Var, values: impl IntoIterator<Item = S::Value>) {
         let vals = values.into_iter().collect::<Vec<_>>();
         if vals.is_empty() {
             panic!("Empty range given for variable {:?}", var);
         }
         self.vars.push(var.clone());
-        self.entries.push(vals);
+        self.entries.push(Domain::new(vals, S::value_index));
+    }
+
+    /// Like `add_column`, but for `Solvomatic::var_range`: builds the column as a `Domain::Range`
+    /// directly from the endpoints, so a huge range never gets materialized into a `Vec` just to
+    /// be handed to `Domain::new`. `Domain::new_range` rejects an empty `lo..=hi`.
+    pub fn add_range_column(&mut self, var: S::Var, lo: i64, hi: i64, to_value: fn(i64) -> S::Value) {
+        let domain = Domain::new_range(lo, hi, to_value);
+        self.vars.push(var.clone());
+        self.entries.push(domain);
     }
 
     pub fn size(&self) -> usize {
@@ -63,18 +104,39 @@ impl<S: State> Table<S> {
     }
 
     fn make_guess(&mut self, var: VarIndex, guess: EntryIndex) {
-        self.entries[var] = vec![self.entries[var].swap_remove(guess)];
+        self.entries[var].make_guess(guess);
     }
 
+    /// Branch on the variable with the smallest remaining domain (picking a solved one, i.e. of
+    /// size 1, only if nothing else is left to branch on). A `Domain::Range` column (see
+    /// `Solvomatic::var_range`) is bisected into two halves instead of branching into one child
+    /// per remaining value, so a million-wide range costs two clones per guess instead of a
+    /// million -- it just takes `log2(width)` guesses to fully narrow instead of one. Bisected
+    /// branches aren't recorded on `trail`, since there's no single `(var, value)` decision to
+    /// record until a branch narrows all the way down to one value.
     pub fn guess(self) -> Vec<Table<S>> {
         let var_to_guess = (0..self.entries.len())
             .max_by_key(|i| self.var_guessing_score(*i))
             .unwrap_or(0);
+
+        if let Domain::Range { .. } = &self.entries[var_to_guess] {
+            if self.entries[var_to_guess].len() > 1 {
+                let (lo_half, hi_half) = self.entries[var_to_guess].bisect();
+                let mut lo_table = self.clone();
+                lo_table.entries[var_to_guess] = lo_half;
+                let mut hi_table = self;
+                hi_table.entries[var_to_guess] = hi_half;
+                return vec![lo_table, hi_table];
+            }
+        }
+
         let num_guesses = self.entries[var_to_guess].len();
         (0..num_guesses)
             .map(|guess| {
                 let mut table = self.clone();
+                let value = table.entries[var_to_guess].iter().nth(guess).unwrap();
                 table.make_guess(var_to_guess, guess);
+                table.trail.push((var_to_guess, value));
                 table
             })
             .collect::<Vec<_>>()
@@ -90,15 +152,15 @@ impl<S: State> Table<S> {
         let var_index = self.vars.iter().position(|v| *v == var).unwrap();
         if let Some((assumed_var, assumed_entry)) = assume {
             if assumed_var == var_index {
-                return constraint
-                    .singleton(param_index, self.entries[var_index][assumed_entry].clone());
+                let value = self.entries[var_index].iter().nth(assumed_entry).unwrap();
+                return constraint.singleton(param_index, value);
             }
         }
 
         let mut values_iter = self.entries[var_index].iter();
-        let mut set = constraint.singleton(param_index, values_iter.next().unwrap().clone());
+        let mut set = constraint.singleton(param_index, values_iter.next().unwrap());
         for value in values_iter {
-            set = constraint.or(set, constraint.singleton(param_index, value.clone()));
+            set = constraint.or(set, constraint.singleton(param_index, value));
         }
         set
     }
@@ -111,7 +173,7 @@ impl<S: State> Table<S> {
     ) -> Vec<YesNoMaybe> {
         let var = &params[param_index];
         let var_index = self.vars.iter().position(|v| v == var).unwrap();
-        let values_iter = self.entries[var_index].iter().cloned();
+        let values_iter = self.entries[var_index].iter();
 
         if params.len() == 1 {
             assert_eq!(param_index, 0);
@@ -139,9 +201,11 @@ impl<S: State> Table<S> {
             .collect()
     }
 
-    // TODO
-    #[allow(unused)]
-    fn eval_constraint<C: Constraint<S::Value>>(
+    /// Whole-table evaluation: does `constraint` hold across every parameter's entire current
+    /// domain (`Yes`), is it unsatisfiable no matter how the domains get pinned down (`No`), or
+    /// is it still undetermined (`Maybe`)? Used to retire constraints once they're fully
+    /// satisfied, rather than per-entry pruning like `eval_constraint_for_all`.
+    pub fn eval_constraint<C: Constraint<S::Value>>(
         &self,
         constraint: &C,
         params: &Vec<S::Var>,
@@ -179,11 +243,28 @@ impl<S: State> Table<S> {
         true
     }
 
+    /// A canonical 64-bit fingerprint of this table's remaining domains, for the transposition
+    /// cache: two tables with the same `entries` fingerprint the same regardless of which var was
+    /// guessed first to reach them, since each column hashes its present values in a canonical
+    /// order rather than whatever order `guess`/`remove` happened to leave them in (see
+    /// `Domain::hash_canonical`, which hashes a `Range` column's endpoints directly instead of
+    /// enumerating it, so a huge `var_range` column is as cheap to fingerprint as a small one).
+    pub fn fingerprint(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        let mut hasher = DefaultHasher::new();
+        for values in &self.entries {
+            values.hash_canonical(&mut hasher);
+        }
+        hasher.finish()
+    }
+
     pub fn into_state(&self, metadata: &S::MetaData) -> S {
         let mut solution = S::new(metadata);
         for (var, values) in self.vars.iter().zip(self.entries.iter()) {
-            if values.len() == 1 {
-                solution.set(var.clone(), values[0].clone());
+            if let Some(value) = values.only() {
+                solution.set(var.clone(), value);
             }
         }
         solution